@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use aoc23::{generate::generate, solve, Task};
+use criterion::{
+    black_box, criterion_group, criterion_main, AxisScale, BenchmarkId, Criterion,
+    PlotConfiguration, SamplingMode,
+};
+
+/// Days whose solvers could grow faster than their input, with the sizes to try.
+const SWEEPS: [(usize, &[usize]); 4] = [
+    (11, &[100, 200, 400, 800]),
+    (12, &[1250, 2500, 5000, 10000]),
+    (16, &[16, 31, 62, 125, 250, 500]),
+    (17, &[31, 62, 125, 250, 500, 1000]),
+];
+
+/// Set to run the sizes of [`LONG`] as well, the whole sweep then takes over an hour.
+const LONG_VAR: &str = "AOC23_LONG_BENCH";
+
+/// Day, size and measurement time in seconds of the sizes that take seconds per solve. They are
+/// sampled flat with one solve per sample, so the time has to cover the ten samples.
+const LONG: [(usize, usize, u64); 6] = [
+    (12, 10000, 20),
+    (16, 250, 90),
+    (16, 500, 1200),
+    (17, 250, 90),
+    (17, 500, 400),
+    (17, 1000, 2400),
+];
+
+fn scaling(c: &mut Criterion) {
+    let long = std::env::var_os(LONG_VAR).is_some();
+    for (day, sizes) in SWEEPS {
+        let mut group = c.benchmark_group(format!("day {day} scaling"));
+        group.plot_config(PlotConfiguration::default().summary_scale(AxisScale::Logarithmic));
+        group.sample_size(10);
+        for &size in sizes {
+            if let Some(&(_, _, secs)) = LONG.iter().find(|l| (l.0, l.1) == (day, size)) {
+                if !long {
+                    continue;
+                }
+                group.sampling_mode(SamplingMode::Flat);
+                group.measurement_time(Duration::from_secs(secs));
+            }
+            let input = generate(day, size, 1);
+            group.bench_with_input(BenchmarkId::from_parameter(size), &input, |b, input| {
+                b.iter(|| solve(black_box(day), black_box(input), Task::Both))
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, scaling);
+criterion_main!(benches);
@@ -0,0 +1,41 @@
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    // the C smoke test compiles for the same target
+    println!(
+        "cargo:rustc-env=AOC23_TARGET={}",
+        env::var("TARGET").unwrap()
+    );
+    println!("cargo:rustc-env=AOC23_HOST={}", env::var("HOST").unwrap());
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+    println!("cargo:rerun-if-changed=inputs");
+    let inputs = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("inputs");
+    let mut entries = vec![];
+    for entry in fs::read_dir(&inputs).expect("inputs directory not found") {
+        let path = entry.unwrap().path();
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        // only dayXX.txt and dayXX_test.txt are inputs
+        let Some(name) = name.strip_prefix("day") else {
+            continue;
+        };
+        let (day, test) = match name.strip_suffix("_test") {
+            Some(day) => (day, true),
+            None => (name, false),
+        };
+        let Ok(day) = day.parse::<usize>() else {
+            continue;
+        };
+        entries.push((day, test, path));
+    }
+    entries.sort();
+    let mut code = String::from("pub static EMBEDDED_INPUTS: &[(usize, bool, &str)] = &[\n");
+    for (day, test, path) in entries {
+        code += &format!("    ({day}, {test}, include_str!({:?})),\n", path);
+    }
+    code += "];\n";
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("embedded_inputs.rs");
+    fs::write(out, code).unwrap();
+}
@@ -0,0 +1,28 @@
+use aoc23::reference::{self, Check};
+fn main() {
+    for size in [5, 10, 20, 30] {
+        for day in reference::DAYS {
+            let (mut agree, mut skip, mut dis, mut err) = (0, 0, 0, 0);
+            let t = std::time::Instant::now();
+            for seed in 0..10 {
+                let input = aoc23::generate::generate(day, size, seed);
+                match reference::cross_check(day, &input) {
+                    Ok(checks) => {
+                        for c in checks {
+                            match c {
+                                Check::Agree(_) => agree += 1,
+                                Check::Skipped(_) => skip += 1,
+                                Check::Disagree { .. } => dis += 1,
+                            }
+                        }
+                    }
+                    Err(_) => err += 1,
+                }
+            }
+            println!(
+                "size {size} day {day}: agree {agree} skip {skip} disagree {dis} err {err} {:?}",
+                t.elapsed()
+            );
+        }
+    }
+}
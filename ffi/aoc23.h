@@ -0,0 +1,29 @@
+/* Generated by aoc23::ffi::header, don't edit it by hand. */
+#ifndef AOC23_H
+#define AOC23_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#define AOC23_DAY_COUNT 25
+
+#define AOC23_TASK_BOTH 0
+#define AOC23_TASK_ONE 1
+#define AOC23_TASK_TWO 2
+
+/* status codes returned by aoc23_solve */
+#define AOC23_OK 0 /* both answers were written */
+#define AOC23_INVALID_DAY 1 /* there is no solver for the day */
+#define AOC23_INVALID_TASK 2 /* the task is not one of AOC23_TASK_* */
+#define AOC23_INVALID_UTF8 3 /* the input is not valid UTF-8 */
+#define AOC23_PARSE_ERROR 4 /* the input is malformed, out1 holds the error message */
+#define AOC23_BUFFER_TOO_SMALL 5 /* an answer and its terminating NUL don't fit into out_len bytes */
+#define AOC23_PANIC 6 /* the solver panicked, this is a bug */
+
+/* Solve a day and write both answers as NUL terminated strings to out1 and out2,
+ * which both have room for out_len bytes. input needs to be valid UTF-8 and doesn't
+ * need to be NUL terminated. The answer of a part that wasn't solved is empty. */
+int32_t aoc23_solve(uint32_t day, uint32_t task, const uint8_t *input, size_t input_len,
+                    char *out1, char *out2, size_t out_len);
+
+#endif
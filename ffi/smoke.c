@@ -0,0 +1,43 @@
+/* Smoke test of the C ABI, compiled and run by `cargo test`. */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc23.h"
+
+#define CHECK(cond)                                                                \
+    if (!(cond)) {                                                                 \
+        fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #cond);   \
+        return 1;                                                                  \
+    }
+
+static int32_t solve(uint32_t day, uint32_t task, const char *input, char *out1, char *out2,
+                     size_t out_len) {
+    return aoc23_solve(day, task, (const uint8_t *)input, strlen(input), out1, out2, out_len);
+}
+
+int main(void) {
+    const char *races = "Time:      7  15   30\nDistance:  9  40  200\n";
+    char out1[64], out2[64];
+
+    CHECK(solve(6, AOC23_TASK_BOTH, races, out1, out2, sizeof out1) == AOC23_OK);
+    CHECK(strcmp(out1, "288") == 0);
+    CHECK(strcmp(out2, "71503") == 0);
+
+    CHECK(solve(6, AOC23_TASK_TWO, races, out1, out2, sizeof out1) == AOC23_OK);
+    CHECK(strcmp(out1, "") == 0);
+    CHECK(strcmp(out2, "71503") == 0);
+
+    CHECK(solve(0, AOC23_TASK_BOTH, races, out1, out2, sizeof out1) == AOC23_INVALID_DAY);
+    CHECK(solve(AOC23_DAY_COUNT + 1, AOC23_TASK_BOTH, races, out1, out2, sizeof out1) ==
+          AOC23_INVALID_DAY);
+    CHECK(solve(6, 3, races, out1, out2, sizeof out1) == AOC23_INVALID_TASK);
+    CHECK(solve(6, AOC23_TASK_BOTH, "\xff", out1, out2, sizeof out1) == AOC23_INVALID_UTF8);
+
+    CHECK(solve(6, AOC23_TASK_BOTH, "Time: x\n", out1, out2, sizeof out1) == AOC23_PARSE_ERROR);
+    CHECK(strlen(out1) > 0);
+
+    CHECK(solve(6, AOC23_TASK_BOTH, races, out1, out2, 3) == AOC23_BUFFER_TOO_SMALL);
+
+    puts("ok");
+    return 0;
+}
@@ -0,0 +1,254 @@
+//! On-disk cache of answers and timings, keyed by day, task and a fingerprint of the input
+//! and the solver. The file has one tab separated entry per line, so it can be read and
+//! edited by hand.
+
+use std::{fs, io, path::PathBuf, sync::Mutex, time::Duration};
+
+use crate::{days, util, Bag, RunOptions, Task, DAY_COUNT};
+
+/// Where the cache lives, relative to the working directory like `./inputs`.
+pub const DEFAULT_PATH: &str = "cache/answers.tsv";
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CacheMode {
+    /// return cached answers, solve and store them on a miss
+    Use,
+    /// always solve and overwrite the cached answers
+    Refresh,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Entry {
+    pub day: usize,
+    /// `1`, `2` or `both`
+    pub task: String,
+    pub fingerprint: u64,
+    pub result1: String,
+    pub result2: String,
+    /// time it took to solve
+    pub time: Duration,
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Self> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [day, task, fingerprint, result1, result2, micros] = fields[..] else {
+            return None;
+        };
+        Some(Self {
+            day: day.parse().ok()?,
+            task: String::from(task),
+            fingerprint: u64::from_str_radix(fingerprint, 16).ok()?,
+            result1: String::from(result1),
+            result2: String::from(result2),
+            time: Duration::from_micros(micros.parse().ok()?),
+        })
+    }
+
+    fn line(&self) -> String {
+        format!(
+            "{}\t{}\t{:016x}\t{}\t{}\t{}\n",
+            self.day,
+            self.task,
+            self.fingerprint,
+            self.result1,
+            self.result2,
+            self.time.as_micros()
+        )
+    }
+}
+
+pub struct AnswerCache {
+    path: PathBuf,
+    mode: CacheMode,
+    entries: Mutex<Vec<Entry>>,
+}
+
+impl AnswerCache {
+    /// Load the cache, a missing file is an empty cache and broken lines are dropped.
+    pub fn load(path: impl Into<PathBuf>, mode: CacheMode) -> io::Result<Self> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Ok(contents) => contents.lines().filter_map(Entry::parse).collect(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            path,
+            mode,
+            entries: Mutex::new(entries),
+        })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let entries = self.entries.lock().unwrap();
+        fs::write(
+            &self.path,
+            entries.iter().map(Entry::line).collect::<String>(),
+        )
+    }
+
+    pub fn entries(&self) -> Vec<Entry> {
+        self.entries.lock().unwrap().clone()
+    }
+
+    /// Cached answers and the time it took to solve them, only in [`CacheMode::Use`].
+    pub(crate) fn get(
+        &self,
+        day: usize,
+        task: Task,
+        fingerprint: u64,
+    ) -> Option<(String, String, Duration)> {
+        if self.mode != CacheMode::Use {
+            return None;
+        }
+        let entries = self.entries.lock().unwrap();
+        let entry = entries
+            .iter()
+            .find(|e| e.day == day && e.task == task_name(task) && e.fingerprint == fingerprint)?;
+        Some((entry.result1.clone(), entry.result2.clone(), entry.time))
+    }
+
+    pub(crate) fn insert(
+        &self,
+        day: usize,
+        task: Task,
+        fingerprint: u64,
+        results: (&str, &str),
+        time: Duration,
+    ) {
+        // tabs and newlines would break the file
+        let clean = |result: &str| result.replace(['\t', '\n'], " ");
+        let entry = Entry {
+            day,
+            task: String::from(task_name(task)),
+            fingerprint,
+            result1: clean(results.0),
+            result2: clean(results.1),
+            time,
+        };
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|e| (e.day, &e.task, e.fingerprint) != (day, &entry.task, fingerprint));
+        entries.push(entry);
+    }
+
+    /// Remove entries that don't match any current input or solver and return how many, answers
+    /// with another bag on day 2 or area on day 24 are removed too.
+    pub fn prune(&self) -> usize {
+        let defaults = RunOptions::new(false, Task::Both);
+        let current = (1..=DAY_COUNT)
+            .flat_map(|day| [(day, false), (day, true)])
+            .filter_map(|(day, test)| {
+                Some(fingerprint(day, &util::find_input(day, test)?, &defaults))
+            })
+            .collect::<Vec<_>>();
+        let mut entries = self.entries.lock().unwrap();
+        let before = entries.len();
+        entries.retain(|e| current.contains(&e.fingerprint));
+        before - entries.len()
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+fn task_name(task: Task) -> &'static str {
+    match task {
+        Task::One => "1",
+        Task::Two => "2",
+        Task::Both => "both",
+    }
+}
+
+/// Sources of the modules that all solvers build on, changing them can change any answer.
+const SHARED_SOURCES: [&str; 5] = [
+    include_str!("days/mod.rs"),
+    include_str!("geometry.rs"),
+    include_str!("parse.rs"),
+    include_str!("types.rs"),
+    include_str!("util.rs"),
+];
+
+/// Hash of the input, the solver source of the day, the shared sources, the crate version and
+/// the options of the day, the bag of day 2 and the area of day 24. Unlike the std hasher
+/// FNV-1a stays the same between builds, so the fingerprints can be stored.
+pub fn fingerprint(day: usize, input: &str, options: &RunOptions) -> u64 {
+    let setting = match day {
+        2 => options.bag.map(Bag::to_string),
+        24 => options.area.map(|area| area.to_string()),
+        _ => None,
+    };
+    let setting = setting.unwrap_or_default();
+    let parts = [
+        env!("CARGO_PKG_VERSION"),
+        days::source(day),
+        input,
+        &setting,
+    ];
+    let mut hash = 0xcbf2_9ce4_8422_2325;
+    let parts = parts.iter().chain(&SHARED_SOURCES);
+    for byte in parts.flat_map(|part| part.bytes().chain([0])) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn entries_round_trip() {
+        let entry = Entry {
+            day: 12,
+            task: String::from("both"),
+            fingerprint: 0xdead_beef,
+            result1: String::from("21"),
+            result2: String::from("525152"),
+            time: Duration::from_micros(1234),
+        };
+        assert_eq!(Entry::parse(entry.line().trim_end()), Some(entry));
+        assert_eq!(Entry::parse("12\tboth\tnot hex\t1\t2\t3"), None);
+    }
+
+    #[test]
+    fn fingerprints_change_with_input_and_day() {
+        let defaults = RunOptions::new(false, Task::Both);
+        assert_eq!(
+            fingerprint(1, "a1", &defaults),
+            fingerprint(1, "a1", &defaults)
+        );
+        assert_ne!(
+            fingerprint(1, "a1", &defaults),
+            fingerprint(1, "a2", &defaults)
+        );
+        assert_ne!(
+            fingerprint(1, "a1", &defaults),
+            fingerprint(2, "a1", &defaults)
+        );
+        let bag = "1 red".parse().unwrap();
+        let options = RunOptions {
+            bag: Some(&bag),
+            area: Some("7,27".parse().unwrap()),
+            ..defaults
+        };
+        assert_ne!(
+            fingerprint(2, "a1", &defaults),
+            fingerprint(2, "a1", &options)
+        );
+        assert_ne!(
+            fingerprint(24, "a1", &defaults),
+            fingerprint(24, "a1", &options)
+        );
+        // only day 2 has a bag and only day 24 an area
+        assert_eq!(
+            fingerprint(1, "a1", &defaults),
+            fingerprint(1, "a1", &options)
+        );
+    }
+}
@@ -1,100 +1,281 @@
-use crate::types::*;
+use std::{collections::VecDeque, fmt};
+
+use crate::{explain::Explanation, types::*};
+
+const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+const GERMAN: [(&str, u32); 9] = [
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+const FRENCH: [(&str, u32); 9] = [
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+const LANGUAGES: [(&str, &[(&str, u32)]); 3] = [
+    ("english", &ENGLISH),
+    ("german", &GERMAN),
+    ("french", &FRENCH),
+];
 
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = &'a str;
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        input
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        Ok(input)
     }
 
-    fn solve1(&self, input: &Self::Input, _test: bool) -> String {
-        let sum = input.lines().fold(0u32, |acc, line| {
-            if line.is_empty() {
-                return acc;
-            }
-            let start = line.chars().find(|c| c.is_ascii_digit()).unwrap() as u8 - b'0';
-            let end = line.chars().rfind(|c| c.is_ascii_digit()).unwrap() as u8 - b'0';
-            let num = start * 10 + end;
-            acc + num as u32
-        });
+    fn solve1(&self, input: &Self::Input, test: bool) -> String {
+        let (sum, warnings) = calibration(input, &[], test);
+        for warning in warnings {
+            test_print!(test, "warning: {warning}");
+        }
         sum.to_string()
     }
 
     fn solve2(&self, input: &Self::Input, test: bool) -> String {
-        let sum = input.lines().fold(0u32, |acc, line| {
-            if line.is_empty() {
-                return acc;
-            }
-            let start = find_first_number(line);
-            let end = find_last_number(line);
-            test_print!(test, "line: {line} start: {start} end: {end}");
-            let num = start * 10 + end;
-            acc + num
-        });
+        let (sum, warnings) = calibration(input, &ENGLISH, test);
+        for warning in warnings {
+            test_print!(test, "warning: {warning}");
+        }
         sum.to_string()
     }
-}
 
-fn find_first_number(mut line: &str) -> u32 {
-    let mut digit = 0u32;
-    let digit_pos = line.chars().position(|c| c.is_ascii_digit());
-    if let Some(pos) = digit_pos {
-        digit = (line.as_bytes()[pos] - b'0') as u32;
-        if pos == 0 {
-            return digit;
+    const EXPLORE_HELP: &'static str =
+        "  words <language|word=n ..>   sum with the digit words of english, german, french or custom ones";
+
+    fn explore(&self, input: &Self::Input, command: &str, args: &[&str]) -> Option<String> {
+        if command != "words" {
+            return None;
         }
-        line = &line[..=pos];
+        let usage = || {
+            String::from(
+                "usage: words <english|german|french> or words <word=n> .. with n from 0 to 9",
+            )
+        };
+        let words = match args {
+            [] => return Some(usage()),
+            [language] if !language.contains('=') => match digit_words(language) {
+                Some(words) => words.to_vec(),
+                None => return Some(format!("unknown language '{language}'")),
+            },
+            custom => {
+                let words = custom.iter().map(|arg| {
+                    let (word, value) = arg.split_once('=').filter(|(w, _)| !w.is_empty())?;
+                    // larger values are no digits and could overflow the sum
+                    Some((word, value.parse().ok().filter(|&v| v <= 9)?))
+                });
+                match words.collect::<Option<Vec<_>>>() {
+                    Some(words) => words,
+                    None => return Some(usage()),
+                }
+            }
+        };
+        Some(calibration(input, &words, false).0.to_string())
     }
-    for num in [
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9),
-    ] {
-        if let Some(pos) = line.find(num.0) {
-            line = &line[..=pos];
-            digit = num.1
-        }
+
+    fn explain(&self, input: &Self::Input) -> Option<Explanation> {
+        let rows = |warnings: Vec<Warning>| {
+            let rows = warnings
+                .iter()
+                .map(|w| vec![w.line.to_string(), w.text.to_string()]);
+            rows.collect()
+        };
+        let (_, digits) = calibration(input, &[], false);
+        let (_, words) = calibration(input, &ENGLISH, false);
+        let explanation = Explanation::default()
+            .section(
+                "lines without digits, skipped in part 1",
+                &["line", "text"],
+                rows(digits),
+            )
+            .section(
+                "lines without digits or digit words, skipped in part 2",
+                &["line", "text"],
+                rows(words),
+            );
+        Some(explanation)
     }
-    digit
 }
 
-fn find_last_number(mut line: &str) -> u32 {
-    let mut digit = 0u32;
-    let digit_pos = line
-        .chars()
-        .rev()
-        .position(|c| c.is_ascii_digit())
-        .map(|i| line.len() - i - 1);
-    if let Some(pos) = digit_pos {
-        digit = (line.as_bytes()[pos] - b'0') as u32;
-        if pos == line.len() {
-            return digit;
+/// A line without a calibration value.
+#[derive(Debug, PartialEq)]
+pub struct Warning<'i> {
+    /// starting at 1
+    pub line: usize,
+    pub text: &'i str,
+}
+
+impl fmt::Display for Warning<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} has no digits: '{}'", self.line, self.text)
+    }
+}
+
+/// Digit words of `english`, `german` or `french`, e.g. for [`calibration_with`].
+pub fn digit_words(language: &str) -> Option<&'static [(&'static str, u32)]> {
+    let (_, words) = LANGUAGES.iter().find(|(name, _)| *name == language)?;
+    Some(words)
+}
+
+/// Calibration sum of day 1 where the words count as digits too, e.g. part 2 uses the english
+/// [`digit_words`], and the lines that were skipped for lacking digits.
+///
+/// Panics if a word has a value above 9.
+pub fn calibration_with<'i>(input: &'i str, words: &[(&str, u32)]) -> (u32, Vec<Warning<'i>>) {
+    assert!(
+        words.iter().all(|&(_, value)| value <= 9),
+        "digit words need values from 0 to 9"
+    );
+    calibration(input, words, false)
+}
+
+/// Sum of the first and last digit of every line as a two digit number, where the words count
+/// as digits too. Lines without any are skipped with a warning, blank ones silently.
+fn calibration<'i>(input: &'i str, words: &[(&str, u32)], test: bool) -> (u32, Vec<Warning<'i>>) {
+    let scanner = Scanner::new(DIGITS.iter().chain(words));
+    let mut sum = 0;
+    let mut warnings = vec![];
+    let mut line = 0;
+    scanner.scan(input, |text, digits| {
+        line += 1;
+        match digits {
+            Some((first, last)) => {
+                test_print!(test, "line: {text} first: {first} last: {last}");
+                sum += first * 10 + last;
+            }
+            None if text.trim().is_empty() => {}
+            None => warnings.push(Warning { line, text }),
         }
-        line = &line[pos..];
+    });
+    (sum, warnings)
+}
+
+/// Aho-Corasick automaton over the bytes of the tokens, finding all of them in a single pass
+/// including overlapping ones like `eightwo`.
+struct Scanner {
+    /// next state for every state and byte
+    next: Vec<[usize; 256]>,
+    /// length and value of the tokens ending in every state
+    matches: Vec<Vec<(usize, u32)>>,
+}
+
+/// Start, length and value of a token.
+type Token = (usize, usize, u32);
+
+impl Scanner {
+    fn new<'w>(tokens: impl IntoIterator<Item = &'w (&'w str, u32)>) -> Self {
+        // trie of the tokens, the root is state 0
+        let mut children = vec![vec![]];
+        let mut matches = vec![vec![]];
+        for &(token, value) in tokens {
+            let mut state = 0;
+            for &byte in token.as_bytes() {
+                state = match children[state].iter().find(|&&(b, _)| b == byte) {
+                    Some(&(_, child)) => child,
+                    None => {
+                        children.push(vec![]);
+                        matches.push(vec![]);
+                        let child = children.len() - 1;
+                        children[state].push((byte, child));
+                        child
+                    }
+                };
+            }
+            matches[state].push((token.len(), value));
+        }
+        // breadth first, so the longest proper suffix of a state is complete before the state
+        // takes over its transitions and matches
+        let mut next = vec![[0; 256]; children.len()];
+        let mut suffix = vec![0; children.len()];
+        let mut queue = VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            if state != 0 {
+                next[state] = next[suffix[state]];
+                let inherited = matches[suffix[state]].clone();
+                matches[state].extend(inherited);
+            }
+            for &(byte, child) in &children[state] {
+                suffix[child] = if state == 0 {
+                    0
+                } else {
+                    next[suffix[state]][byte as usize]
+                };
+                next[state][byte as usize] = child;
+                queue.push_back(child);
+            }
+        }
+        Self { next, matches }
     }
-    for num in [
-        ("one", 1),
-        ("two", 2),
-        ("three", 3),
-        ("four", 4),
-        ("five", 5),
-        ("six", 6),
-        ("seven", 7),
-        ("eight", 8),
-        ("nine", 9),
-    ] {
-        if let Some(pos) = line.rfind(num.0) {
-            line = &line[pos..];
-            digit = num.1
+
+    /// Call `found` with every line and the values of its first and last token, None if it has
+    /// none. Tokens are ordered by where they start, the longer one wins on the same start.
+    fn scan<'i>(&self, input: &'i str, mut found: impl FnMut(&'i str, Option<(u32, u32)>)) {
+        let mut state = 0;
+        let mut line_start = 0;
+        let (mut first, mut last) = (None::<Token>, None::<Token>);
+        for (i, &byte) in input.as_bytes().iter().enumerate() {
+            if byte == b'\n' {
+                found(
+                    &input[line_start..i],
+                    first.zip(last).map(|(f, l)| (f.2, l.2)),
+                );
+                (state, line_start, first, last) = (0, i + 1, None, None);
+                continue;
+            }
+            state = self.next[state][byte as usize];
+            for &(len, value) in &self.matches[state] {
+                let token = (i + 1 - len, len, value);
+                if first.is_none_or(|(start, l, _)| (token.0, !len) < (start, !l)) {
+                    first = Some(token);
+                }
+                if last.is_none_or(|(start, l, _)| (token.0, len) > (start, l)) {
+                    last = Some(token);
+                }
+            }
+        }
+        if line_start < input.len() {
+            found(
+                &input[line_start..],
+                first.zip(last).map(|(f, l)| (f.2, l.2)),
+            );
         }
     }
-    digit
 }
@@ -1,73 +1,224 @@
-use crate::types::*;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    str::FromStr,
+};
 
-pub struct Solver;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, char},
+    combinator::map,
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair},
+    IResult,
+};
 
-impl<'a> DaySolver<'a> for Solver {
-    type Input = &'a str;
+use crate::{
+    parse::{self, lines, number},
+    types::*,
+};
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        input
+/// The bag of part 1 unless the solver is given another one.
+const BAG: [(&str, u64); 3] = [("red", 12), ("green", 13), ("blue", 14)];
+/// Part 2 multiplies the minimum counts of these colors.
+const POWER_COLORS: [&str; 3] = ["red", "green", "blue"];
+
+#[derive(Default)]
+pub struct Solver<'b> {
+    /// replaces [`BAG`] in part 1
+    pub bag: Option<&'b Bag>,
+}
+
+/// Cubes in a bag, e.g. `12 red, 13 green, 14 blue`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bag(BTreeMap<String, u64>);
+
+impl Bag {
+    /// Number of cubes of the color, 0 for colors that aren't in the bag.
+    pub fn count(&self, color: &str) -> u64 {
+        self.0.get(color).copied().unwrap_or(0)
     }
+}
 
-    fn solve1(&self, input: &Self::Input, test: bool) -> String {
-        let mut sum = 0;
-        let mut game_id = 1;
-        for line in input.lines() {
-            if game_possible(line, 12, 13, 14, test) {
-                sum += game_id;
-            }
-            game_id += 1;
-        }
-        sum.to_string()
+/// The bag of part 1.
+impl Default for Bag {
+    fn default() -> Self {
+        Self(BAG.map(|(color, n)| (String::from(color), n)).into())
     }
+}
 
-    fn solve2(&self, input: &Self::Input, _test: bool) -> String {
-        let mut sum = 0;
-        for line in input.lines() {
-            let (a, b, c) = max_counts(line);
-            sum += a * b * c;
-        }
-        sum.to_string()
+impl FromStr for Bag {
+    type Err = ParseError;
+
+    fn from_str(bag: &str) -> Result<Self, Self::Err> {
+        let bag = parse::parse_all(bag, cubes)?;
+        Ok(Self::from(&bag))
     }
 }
 
-fn game_possible(line: &str, red: u32, green: u32, blue: u32, test: bool) -> bool {
-    let (max_red, max_green, max_blue) = max_counts(line);
-    test_print!(
-        test,
-        "{line}\n    max_red: {max_red}, max_green: {max_green}, max_blue: {max_blue}"
-    );
-    red >= max_red && green >= max_green && blue >= max_blue
+impl From<&Cubes<'_>> for Bag {
+    fn from(cubes: &Cubes) -> Self {
+        let counts = cubes.iter().map(|(color, &n)| (color.to_string(), n));
+        Self(counts.collect())
+    }
 }
 
-fn max_counts(line: &str) -> (u32, u32, u32) {
-    let draws = line.split(':').last().unwrap().split(';');
-    let mut max_red = 0;
-    let mut max_green = 0;
-    let mut max_blue = 0;
-    for draw in draws {
-        for cubes in draw.split(',') {
-            let (num, color) = cubes.trim().split_once(' ').unwrap();
-            let num = num.parse().unwrap();
-            match color {
-                "red" => {
-                    if num > max_red {
-                        max_red = num;
-                    }
+/// Sorted by color, so equal bags look the same.
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = self.0.iter().map(|(color, n)| format!("{n} {color}"));
+        write!(f, "{}", counts.collect::<Vec<_>>().join(", "))
+    }
+}
+
+/// Number of cubes of every color, as a u64 so that adding up large counts can't overflow.
+type Cubes<'a> = HashMap<&'a str, u64>;
+
+/// A game with the id from its text and the cubes of every draw.
+#[derive(Debug)]
+pub struct Game<'a> {
+    id: u32,
+    draws: Vec<Cubes<'a>>,
+}
+
+impl<'a> DaySolver<'a> for Solver<'_> {
+    type Input = Vec<Game<'a>>;
+
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        Game::parse_all(input)
+    }
+
+    fn solve1(&self, games: &Self::Input, test: bool) -> String {
+        let default = Bag::default();
+        let bag = self.bag.unwrap_or(&default);
+        let possible = games.iter().filter(|game| {
+            test_print!(test, "game {}: minimum bag {}", game.id, game.min_bag());
+            game.possible_with(bag)
+        });
+        possible
+            .map(|game| u64::from(game.id))
+            .sum::<u64>()
+            .to_string()
+    }
+
+    fn solve2(&self, games: &Self::Input, _test: bool) -> String {
+        let mut power = games.iter().map(|game| {
+            let bag = game.min_bag();
+            let counts = POWER_COLORS.map(|color| bag.count(color));
+            counts
+                .iter()
+                .try_fold(1u64, |power, &n| power.checked_mul(n))
+        });
+        let sum = power.try_fold(0u64, |sum, power| sum.checked_add(power?));
+        sum.map_or(String::from("overflow"), |sum| sum.to_string())
+    }
+
+    const EXPLORE_HELP: &'static str = concat!(
+        "  possible <n color, ..>       ids of the games possible with the bag\n",
+        "  min <game>                   fewest cubes of each color that make the game possible\n",
+        "  stats                        draws, total and maximum count of every color"
+    );
+
+    fn explore(&self, games: &Self::Input, command: &str, args: &[&str]) -> Option<String> {
+        let output = match command {
+            "possible" => match args.join(" ").parse::<Bag>() {
+                Ok(bag) => {
+                    let possible = games.iter().filter(|game| game.possible_with(&bag));
+                    let ids = possible.map(|game| game.id.to_string()).collect::<Vec<_>>();
+                    format!("{} possible games: {}", ids.len(), ids.join(", "))
                 }
-                "green" => {
-                    if num > max_green {
-                        max_green = num;
-                    }
+                Err(e) => e.to_string(),
+            },
+            "min" => {
+                let id = args.first().and_then(|id| id.parse().ok());
+                match games.iter().find(|game| Some(game.id) == id) {
+                    Some(game) => game.min_bag().to_string(),
+                    None => String::from("usage: min <game id>"),
                 }
-                "blue" => {
-                    if num > max_blue {
-                        max_blue = num;
-                    }
+            }
+            "stats" => {
+                let mut lines = vec![];
+                for (color, stats) in Game::color_stats(games) {
+                    lines.push(format!(
+                        "{color}: {} draws, {} cubes in total, at most {}",
+                        stats.draws, stats.total, stats.max
+                    ));
                 }
-                _ => {}
+                lines.join("\n")
             }
+            _ => return None,
+        };
+        Some(output)
+    }
+}
+
+/// e.g. `3 blue, 4 red`, cubes of the same color are added up
+fn cubes(input: &str) -> IResult<&str, Cubes<'_>> {
+    let count = separated_pair(number::<u32>, char(' '), alpha1);
+    map(separated_list1(tag(", "), count), |counts| {
+        let mut cubes = Cubes::new();
+        for (n, color) in counts {
+            *cubes.entry(color).or_default() += u64::from(n);
         }
+        cubes
+    })(input)
+}
+
+/// e.g. `Game 1: 3 blue, 4 red; 1 red, 2 green`
+fn game(input: &str) -> IResult<&str, Game<'_>> {
+    map(
+        pair(
+            delimited(tag("Game "), number, tag(": ")),
+            separated_list1(tag("; "), cubes),
+        ),
+        |(id, draws)| Game { id, draws },
+    )(input)
+}
+
+impl<'a> Game<'a> {
+    /// Parse one game per line, e.g. `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+    pub fn parse_all(input: &'a str) -> Result<Vec<Self>, ParseError> {
+        parse::parse_all(input, lines(game))
     }
-    (max_red, max_green, max_blue)
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Whether no draw shows more cubes of a color than the bag holds.
+    pub fn possible_with(&self, bag: &Bag) -> bool {
+        self.draws
+            .iter()
+            .all(|draw| draw.iter().all(|(color, &n)| n <= bag.count(color)))
+    }
+
+    /// The fewest cubes of every color that make the game possible.
+    pub fn min_bag(&self) -> Bag {
+        let mut bag = Cubes::new();
+        for (&color, &n) in self.draws.iter().flatten() {
+            let max = bag.entry(color).or_default();
+            *max = n.max(*max);
+        }
+        Bag::from(&bag)
+    }
+
+    /// Draws, total and maximum count of every color over all games.
+    pub fn color_stats(games: &[Self]) -> BTreeMap<&'a str, ColorStats> {
+        let mut stats = BTreeMap::<_, ColorStats>::new();
+        for (&color, &n) in games.iter().flat_map(|game| game.draws.iter().flatten()) {
+            let stats = stats.entry(color).or_default();
+            stats.draws += 1;
+            stats.total += n;
+            stats.max = stats.max.max(n);
+        }
+        stats
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct ColorStats {
+    /// draws showing the color
+    pub draws: usize,
+    pub total: u64,
+    pub max: u64,
 }
@@ -1,13 +1,18 @@
-use crate::types::*;
+use crate::{
+    parse::{self, grid},
+    types::*,
+};
 
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = (&'a str, usize, Vec<Number>);
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        // only check the shape, the solvers work on the raw input
+        parse::parse_all(input, grid(|c: char| c.is_ascii_graphic().then_some(c)))?;
         let line_length = input.find('\n').unwrap() + 1;
-        (input, line_length, find_numbers(input, line_length))
+        Ok((input, line_length, find_numbers(input, line_length)?))
     }
 
     fn solve1(&self, inp: &Self::Input, test: bool) -> String {
@@ -16,7 +21,7 @@ impl<'a> DaySolver<'a> for Solver {
         let mut sum = 0;
         for num in numbers {
             if touches_symbol(input, *line_length, num.x, num.y, num.len) {
-                sum += num.val;
+                sum += num.val as u64;
             }
         }
         sum.to_string()
@@ -34,7 +39,7 @@ impl<'a> DaySolver<'a> for Solver {
                 let numbers = touching_numbers(numbers, x as isize, y as isize);
                 test_print!(test, "x: {x} y: {y} touching: {numbers:?}");
                 if numbers.len() == 2 {
-                    sum += numbers[0] * numbers[1];
+                    sum += numbers[0] as u64 * numbers[1] as u64;
                 }
             }
         }
@@ -75,7 +80,7 @@ pub struct Number {
     val: u32,
 }
 
-fn find_numbers(input: &str, line_length: usize) -> Vec<Number> {
+fn find_numbers(input: &str, line_length: usize) -> Result<Vec<Number>, ParseError> {
     let mut numbers = vec![];
     let mut num_length = 0;
     let mut number = 0;
@@ -83,6 +88,9 @@ fn find_numbers(input: &str, line_length: usize) -> Vec<Number> {
         if c.is_ascii_digit() {
             num_length += 1;
             number = number * 10 + (c - b'0') as u32;
+            if num_length > 5 {
+                return Err(ParseError::new(input, &input[i..], "number too long"));
+            }
         } else {
             if num_length == 0 {
                 continue;
@@ -97,7 +105,7 @@ fn find_numbers(input: &str, line_length: usize) -> Vec<Number> {
             number = 0;
         }
     }
-    numbers
+    Ok(numbers)
 }
 
 /// check if number ending at i touches a symbol
@@ -132,10 +140,13 @@ fn is_symbol(input: &str, line_length: usize, x: isize, y: isize) -> bool {
     if x < 0 || x as usize > line_length - 2 {
         return false;
     }
-    // check top or bottom outside
-    if y < 0 || y as usize > line_length - 2 {
+    // check top outside
+    if y < 0 {
         return false;
     }
-    let char = input.as_bytes()[line_length * y as usize + x as usize];
+    // grid does not have to be square
+    let Some(&char) = input.as_bytes().get(line_length * y as usize + x as usize) else {
+        return false;
+    };
     !char.is_ascii_digit() && char != b'.'
 }
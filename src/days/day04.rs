@@ -1,4 +1,14 @@
-use crate::types::*;
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, space0, space1},
+    sequence::{delimited, preceded, separated_pair, tuple},
+    IResult,
+};
+
+use crate::{
+    parse::{self, lines, numbers},
+    types::*,
+};
 
 pub struct Solver;
 
@@ -10,24 +20,8 @@ pub struct Card {
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Card>;
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        let mut cards = Vec::with_capacity(input.lines().count());
-        for line in input.lines() {
-            if line.is_empty() {
-                continue;
-            }
-            let numbers = line.split_once(':').unwrap().1.trim();
-            let (winning, yours) = numbers.split_once('|').unwrap();
-            let winning = winning.trim().split(' ').filter(|s| !s.is_empty());
-            let yours = yours.trim().split(' ').filter(|s| !s.is_empty());
-            let winning = winning.map(|n| n.trim().parse::<u8>().unwrap()).collect();
-            let yours = yours.map(|n| n.trim().parse::<u8>().unwrap()).collect();
-            cards.push(Card {
-                winning_numbers: winning,
-                your_numbers: yours,
-            })
-        }
-        cards
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        parse::parse_all(input, lines(card))
     }
 
     fn solve1(&self, cards: &Self::Input, _test: bool) -> String {
@@ -43,16 +37,48 @@ impl<'a> DaySolver<'a> for Solver {
     }
 
     fn solve2(&self, cards: &Self::Input, test: bool) -> String {
-        let mut card_count = vec![1; cards.len()];
-        for (i, card) in cards.iter().take(cards.len() - 1).enumerate() {
-            let matches = count_matches(card);
-            for pos in i + 1..=std::cmp::min(i + matches as usize, cards.len() - 1) {
-                card_count[pos] += card_count[i]
-            }
-        }
+        let card_count = copies(cards);
         test_print!(test, "{card_count:?}");
         card_count.iter().sum::<u32>().to_string()
     }
+
+    fn explain(&self, cards: &Self::Input) -> Option<Explanation> {
+        let rows = cards
+            .iter()
+            .zip(copies(cards))
+            .enumerate()
+            .map(|(i, (card, copies))| {
+                let matches = count_matches(card);
+                let points = if matches == 0 {
+                    0
+                } else {
+                    2u32.pow(matches - 1)
+                };
+                vec![
+                    (i + 1).to_string(),
+                    matches.to_string(),
+                    points.to_string(),
+                    copies.to_string(),
+                ]
+            });
+        Some(Explanation::default().section(
+            "cards, each match wins a copy of one of the following cards",
+            &["card", "matches", "points", "copies"],
+            rows.collect(),
+        ))
+    }
+}
+
+/// Number of copies of each card after all cards are scratched.
+fn copies(cards: &[Card]) -> Vec<u32> {
+    let mut card_count = vec![1; cards.len()];
+    for (i, card) in cards.iter().take(cards.len() - 1).enumerate() {
+        let matches = count_matches(card);
+        for pos in i + 1..=std::cmp::min(i + matches as usize, cards.len() - 1) {
+            card_count[pos] += card_count[i]
+        }
+    }
+    card_count
 }
 
 fn count_matches(card: &Card) -> u32 {
@@ -61,3 +87,18 @@ fn count_matches(card: &Card) -> u32 {
         .filter(|n| card.winning_numbers.contains(n))
         .count() as u32
 }
+
+/// e.g. `Card 1: 41 48 83 | 83 86  6`
+fn card(input: &str) -> IResult<&str, Card> {
+    let (rest, (winning_numbers, your_numbers)) = preceded(
+        tuple((tag("Card"), space1, digit1, char(':'), space0)),
+        separated_pair(numbers, delimited(space1, char('|'), space1), numbers),
+    )(input)?;
+    Ok((
+        rest,
+        Card {
+            winning_numbers,
+            your_numbers,
+        },
+    ))
+}
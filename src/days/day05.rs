@@ -1,12 +1,14 @@
 use nom::{
     bytes::complete::{tag, take_until},
-    character::complete::{self, multispace0},
-    multi::{many1, separated_list0},
-    sequence::{delimited, terminated, tuple},
+    character::complete::{newline, space1},
+    sequence::{preceded, tuple},
     IResult,
 };
 
-use crate::types::*;
+use crate::{
+    parse::{self, labeled_numbers, lines, number},
+    types::*,
+};
 
 pub struct Solver;
 
@@ -32,14 +34,19 @@ impl SeedMaps {
         self.hum_to_loc.map(hum)
     }
 
-    fn loc_to_seed(&self, id: u64) -> Option<u64> {
-        let hum = self.hum_to_loc.map_rev(id).unwrap_or(id);
-        let temp = self.temp_to_hum.map_rev(hum).unwrap_or(hum);
-        let light = self.light_to_temp.map_rev(temp).unwrap_or(temp);
-        let water = self.water_to_light.map_rev(light).unwrap_or(light);
-        let fert = self.fert_to_water.map_rev(water).unwrap_or(water);
-        let soil = self.soil_to_fert.map_rev(fert).unwrap_or(fert);
-        self.seed_to_soil.map_rev(soil)
+    /// Map ranges of seeds to ranges of locations, ranges are half open.
+    fn seed_ranges_to_loc(&self, ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+        [
+            &self.seed_to_soil,
+            &self.soil_to_fert,
+            &self.fert_to_water,
+            &self.water_to_light,
+            &self.light_to_temp,
+            &self.temp_to_hum,
+            &self.hum_to_loc,
+        ]
+        .iter()
+        .fold(ranges, |ranges, mapper| mapper.map_ranges(ranges))
     }
 }
 
@@ -69,27 +76,41 @@ impl Mapper {
         }
         input
     }
-    fn map_rev(&self, input: u64) -> Option<u64> {
-        for r in self.ranges.iter() {
-            if input < r.out_start {
-                continue;
-            }
-            let offset = input - r.out_start;
-            if offset >= r.len {
-                continue;
+
+    /// Split the ranges at the borders of the mapped ranges and map every piece.
+    fn map_ranges(&self, mut todo: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+        let mut mapped = Vec::with_capacity(todo.len());
+        'todo: while let Some((start, end)) = todo.pop() {
+            for r in self.ranges.iter() {
+                let lo = start.max(r.in_start);
+                let hi = end.min(r.in_start.saturating_add(r.len));
+                if lo >= hi {
+                    continue;
+                }
+                mapped.push((
+                    r.out_start + (lo - r.in_start),
+                    r.out_start + (hi - r.in_start),
+                ));
+                // parts left and right of the mapped range may be mapped by another range
+                if start < lo {
+                    todo.push((start, lo));
+                }
+                if hi < end {
+                    todo.push((hi, end));
+                }
+                continue 'todo;
             }
-            return Some(r.in_start + offset);
+            mapped.push((start, end));
         }
-        None
+        mapped
     }
 }
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = SeedMaps;
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        let (_, parsed) = nom_parse(input).unwrap();
-        parsed
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        parse::parse_all(input, nom_parse)
     }
 
     fn solve1(&self, input: &Self::Input, test: bool) -> String {
@@ -103,73 +124,33 @@ impl<'a> DaySolver<'a> for Solver {
     }
 
     fn solve2(&self, input: &Self::Input, test: bool) -> String {
-        let seeds = input.seeds.clone();
-        let pairs = seeds.chunks(2).collect::<Vec<_>>();
-        test_print!(test, "seeds: {}, pairs: {}", input.seeds.len(), pairs.len());
-        // get starting max value by using part 1 rules
-        let mut max = input
+        let ranges = input
             .seeds
+            .chunks_exact(2)
+            .map(|pair| (pair[0], pair[0].saturating_add(pair[1])))
+            .collect::<Vec<_>>();
+        test_print!(test, "seed ranges: {ranges:?}");
+        let locations = input.seed_ranges_to_loc(ranges);
+        test_print!(test, "location ranges: {}", locations.len());
+        locations
             .iter()
-            .map(|seed| input.seed_to_loc(*seed))
+            .filter(|(start, end)| start < end)
+            .map(|(start, _)| start)
             .min()
-            .unwrap();
-        // iterate over possible locations and find smallest possible seed
-        // start with big steps and get smaller after overshooting
-        let mut min = 0;
-        let mut loc = 0;
-        let mut step = 10000;
-        loop {
-            if loc < max {
-                loc += step;
-            } else if step == 1 {
-                break;
-            } else {
-                step /= 10;
-                loc = min;
-            }
-            if let Some(seed) = input.loc_to_seed(loc) {
-                // check if valid seed
-                if pairs
-                    .iter()
-                    .any(|pair| seed >= pair[0] && seed < pair[0] + pair[1])
-                {
-                    test_print!(test, "loc: {loc}, seed: {seed}");
-                    min = loc - step;
-                    max = loc;
-                }
-            }
-        }
-        loc.to_string()
+            .map_or(String::from("no solution"), u64::to_string)
     }
 }
 
-fn seed_list(input: &str) -> IResult<&str, Vec<u64>> {
-    let (input, _) = tag("seeds:")(input)?;
-    let (rest, seeds) = delimited(
-        multispace0,
-        separated_list0(complete::char(' '), complete::u64),
-        complete::newline,
-    )(input)?;
-    Ok((rest, seeds))
-}
-
+// skips everything up to the map header, including the blank line after the previous block
 fn mapper(input: &str) -> IResult<&str, Mapper> {
-    let (input, _) = tuple((take_until("map:"), tag("map:\n")))(input)?;
-    let (rest, ranges) = many1(range_map)(input)?;
+    let header = tuple((take_until("map:"), tag("map:"), newline));
+    let (rest, ranges) = preceded(header, lines(range_map))(input)?;
     Ok((rest, Mapper { ranges }))
 }
 
 fn range_map(input: &str) -> IResult<&str, Range> {
-    let (rest, (out_start, _, in_start, _, len)) = terminated(
-        tuple((
-            complete::u64,
-            complete::space1,
-            complete::u64,
-            complete::space1,
-            complete::u64,
-        )),
-        complete::newline,
-    )(input)?;
+    let (rest, (out_start, _, in_start, _, len)) =
+        tuple((number, space1, number, space1, number))(input)?;
     Ok((
         rest,
         Range {
@@ -194,7 +175,14 @@ fn nom_parse(input: &str) -> IResult<&str, SeedMaps> {
             hum_to_loc,
         ),
     ) = tuple((
-        seed_list, mapper, mapper, mapper, mapper, mapper, mapper, mapper,
+        labeled_numbers("seeds:"),
+        mapper,
+        mapper,
+        mapper,
+        mapper,
+        mapper,
+        mapper,
+        mapper,
     ))(input)?;
     Ok((
         input,
@@ -1,42 +1,60 @@
-use nom::{
-    bytes::complete::{is_not, tag},
-    character::complete::{self, newline},
-    multi::separated_list1,
-    sequence::{delimited, tuple},
-    IResult,
-};
+use nom::{character::complete::newline, sequence::separated_pair};
 
-use crate::types::*;
+use crate::{
+    parse::{self, labeled_numbers},
+    types::*,
+};
 
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
-    type Input = &'a str;
+    type Input = Vec<(u64, u64)>;
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        input
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        let (times, distances) = parse::parse_all(
+            input,
+            separated_pair(
+                labeled_numbers("Time:"),
+                newline,
+                labeled_numbers("Distance:"),
+            ),
+        )?;
+        Ok(times.into_iter().zip(distances).collect())
     }
 
-    fn solve1(&self, input: &Self::Input, test: bool) -> String {
-        let (_, races) = nom_parse_1(input).unwrap();
+    fn solve1(&self, races: &Self::Input, test: bool) -> String {
         test_print!(test, "races: {races:?}");
-        let mut res = 1;
-        for (time, max_dist) in races {
-            let min = (1..time).find(|t| t * (time - t) > max_dist).unwrap();
-            let max = (min..time).find(|t| t * (time - t) <= max_dist).unwrap();
-            test_print!(test, "min: {min}, max: {max}");
-            res *= max - min;
+        let product = races.iter().try_fold(1u64, |res, &(time, max_dist)| {
+            res.checked_mul(ways_to_win(time, max_dist, test))
+        });
+        product.map_or(String::from("overflow"), |res| res.to_string())
+    }
+
+    fn solve2(&self, races: &Self::Input, test: bool) -> String {
+        // the spaces between the numbers are ignored in part 2
+        let race = races.iter().try_fold((0, 0), |(time, dist), (t, d)| {
+            Some((concat_digits(time, *t)?, concat_digits(dist, *d)?))
+        });
+        match race {
+            Some((time, max_dist)) => ways_to_win(time, max_dist, test).to_string(),
+            None => String::from("overflow"),
         }
-        res.to_string()
     }
+}
 
-    fn solve2(&self, input: &Self::Input, test: bool) -> String {
-        let (_, (time, max_dist)) = nom_parse_2(input).unwrap();
-        let min = binary_search(1, time, |t| t * (time - t) > max_dist);
-        let max = binary_search_right(min, time, |t| t * (time - t) >= max_dist);
-        test_print!(test, "min: {min}, max: {max}");
-        (max - min + 1).to_string()
+/// Number of hold times that beat the distance. The distance is symmetric around half
+/// the race time, so only the shortest winning hold time has to be searched.
+fn ways_to_win(time: u64, max_dist: u64, test: bool) -> u64 {
+    // a distance too large for u64 beats any record
+    let wins = |t: u64| t.checked_mul(time - t).is_none_or(|dist| dist > max_dist);
+    let half = time / 2;
+    if !wins(half) {
+        return 0;
     }
+    let min = binary_search(0, half, wins);
+    let max = time - min;
+    test_print!(test, "min: {min}, max: {max}");
+    max - min + 1
 }
 
 fn binary_search(mut min: u64, mut max: u64, valid: impl Fn(u64) -> bool) -> u64 {
@@ -52,42 +70,8 @@ fn binary_search(mut min: u64, mut max: u64, valid: impl Fn(u64) -> bool) -> u64
     min
 }
 
-fn binary_search_right(mut min: u64, mut max: u64, valid: impl Fn(u64) -> bool) -> u64 {
-    let mut mid;
-    while min < max {
-        mid = (max + min) / 2;
-        if valid(mid) {
-            min = mid + 1;
-        } else {
-            max = mid - 1;
-        }
-    }
-    max
-}
-
-fn nom_parse_2(input: &str) -> IResult<&str, (u64, u64)> {
-    let (_, (time, distance)) = tuple((num("Time:"), num("Distance:")))(input)?;
-    Ok(("", (time, distance)))
-}
-
-fn num(key: &'static str) -> impl Fn(&str) -> IResult<&str, u64> {
-    move |input| {
-        let (rest, numbers) = delimited(tag(key), is_not("\n"), complete::newline)(input)?;
-        let num = numbers.replace(' ', "").parse().unwrap();
-        Ok((rest, num))
-    }
-}
-
-fn nom_parse_1(input: &str) -> IResult<&str, Vec<(u32, u32)>> {
-    let (_, (times, _, distances)) =
-        tuple((num_list("Time:"), newline, num_list("Distance:")))(input)?;
-    Ok(("", times.into_iter().zip(distances).collect()))
-}
-
-fn num_list(key: &'static str) -> impl Fn(&str) -> IResult<&str, Vec<u32>> {
-    move |input| {
-        let (input, _) = tuple((tag(key), complete::space0))(input)?;
-        let (rest, seeds) = separated_list1(complete::space1, complete::u32)(input)?;
-        Ok((rest, seeds))
-    }
+/// None if the result overflows.
+fn concat_digits(a: u64, b: u64) -> Option<u64> {
+    let shift = 10u64.checked_pow(b.checked_ilog10().unwrap_or(0) + 1)?;
+    a.checked_mul(shift)?.checked_add(b)
 }
@@ -2,7 +2,18 @@ use std::cmp::Ordering;
 
 use strum::{EnumIter, IntoEnumIterator};
 
-use crate::types::*;
+use nom::{
+    character::complete::{one_of, space1},
+    combinator::recognize,
+    multi::count,
+    sequence::separated_pair,
+    IResult,
+};
+
+use crate::{
+    parse::{self, lines, number},
+    types::*,
+};
 
 pub struct Solver;
 
@@ -49,6 +60,24 @@ impl CardValue {
             _ => panic!("invalid card"),
         }
     }
+
+    fn symbol(&self) -> char {
+        match self {
+            Self::Joker | Self::Jack => 'J',
+            Self::Num2 => '2',
+            Self::Num3 => '3',
+            Self::Num4 => '4',
+            Self::Num5 => '5',
+            Self::Num6 => '6',
+            Self::Num7 => '7',
+            Self::Num8 => '8',
+            Self::Num9 => '9',
+            Self::Num10 => 'T',
+            Self::Queen => 'Q',
+            Self::King => 'K',
+            Self::Ace => 'A',
+        }
+    }
 }
 
 #[derive(Debug, Ord, Eq, PartialEq, PartialOrd)]
@@ -160,19 +189,17 @@ fn get_rank(cards: &[CardValue; 5]) -> Rank {
 impl<'a> DaySolver<'a> for Solver {
     type Input = (Vec<Hand>, Vec<Hand>);
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        let mut hands1 = Vec::with_capacity(input.lines().count());
-        let mut hands2 = Vec::with_capacity(input.lines().count());
-        for line in input.lines() {
-            if line.is_empty() {
-                continue;
-            }
-            let (cards, bid) = line.split_once(' ').unwrap();
-            let bid = bid.trim().parse().unwrap();
-            hands1.push(Hand::new(cards, bid, false));
-            hands2.push(Hand::new(cards, bid, true));
-        }
-        (hands1, hands2)
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        let parsed = parse::parse_all(input, lines(hand))?;
+        let hands1 = parsed
+            .iter()
+            .map(|&(cards, bid)| Hand::new(cards, bid.into(), false))
+            .collect();
+        let hands2 = parsed
+            .iter()
+            .map(|&(cards, bid)| Hand::new(cards, bid.into(), true))
+            .collect();
+        Ok((hands1, hands2))
     }
 
     fn solve1(&self, input: &Self::Input, _test: bool) -> String {
@@ -184,15 +211,48 @@ impl<'a> DaySolver<'a> for Solver {
         let (_, hands) = input;
         sum_hands(hands).to_string()
     }
+
+    fn explain(&self, input: &Self::Input) -> Option<Explanation> {
+        let rows = |hands: &[Hand]| {
+            let hands = ranked(hands).into_iter().enumerate();
+            hands
+                .map(|(i, hand)| {
+                    vec![
+                        (i + 1).to_string(),
+                        hand.cards.iter().map(CardValue::symbol).collect(),
+                        format!("{:?}", hand.rank),
+                        hand.bid.to_string(),
+                        ((i as u64 + 1) * hand.bid as u64).to_string(),
+                    ]
+                })
+                .collect()
+        };
+        let columns = ["rank", "hand", "type", "bid", "winnings"];
+        Some(
+            Explanation::default()
+                .section("part 1, weakest hand first", &columns, rows(&input.0))
+                .section("part 2, J is a joker", &columns, rows(&input.1)),
+        )
+    }
 }
 
-fn sum_hands(hands: &[Hand]) -> u32 {
+/// Hands from the weakest to the strongest.
+fn ranked(hands: &[Hand]) -> Vec<&Hand> {
     let mut hands = hands.iter().collect::<Vec<_>>();
     hands.sort_unstable();
+    hands
+}
+
+fn sum_hands(hands: &[Hand]) -> u64 {
     let mut sum = 0;
-    for (rank, hand) in hands.iter().enumerate() {
+    for (rank, hand) in ranked(hands).iter().enumerate() {
         let rank = rank + 1;
-        sum += rank as u32 * hand.bid;
+        sum += rank as u64 * hand.bid as u64;
     }
     sum
 }
+
+/// cards and bid, e.g. `32T3K 765`
+fn hand(input: &str) -> IResult<&str, (&str, u16)> {
+    separated_pair(recognize(count(one_of("23456789TJQKA"), 5)), space1, number)(input)
+}
@@ -2,13 +2,16 @@ use std::collections::HashMap;
 
 use nom::{
     bytes::complete::{is_a, tag},
-    character::complete::{alphanumeric1, multispace1, newline},
-    multi::separated_list1,
+    character::complete::alphanumeric1,
     sequence::tuple,
     IResult,
 };
 
-use crate::types::*;
+use crate::{
+    parse::{self, lines},
+    types::*,
+    util,
+};
 
 pub struct Solver;
 
@@ -25,7 +28,7 @@ pub struct Map<'a> {
     nodes: HashMap<&'a str, Node<'a>>,
 }
 
-fn node(input: &str) -> IResult<&str, Node> {
+fn node(input: &str) -> IResult<&str, Node<'_>> {
     let (rest, (name, _, left, _, right, _)) = tuple((
         alphanumeric1,
         tag(" = ("),
@@ -37,34 +40,82 @@ fn node(input: &str) -> IResult<&str, Node> {
     Ok((rest, Node { name, left, right }))
 }
 
-fn nom_parse(input: &str) -> IResult<&str, Map> {
-    let (rest, (directions, _, mut nodes)) =
-        tuple((is_a("RL"), multispace1, separated_list1(newline, node)))(input)?;
-    let directions = directions.bytes().collect();
-    let mut node_map = HashMap::with_capacity(nodes.len());
-    nodes.drain(0..nodes.len()).for_each(|node| {
-        node_map.insert(node.name, node);
-    });
-    Ok((
-        rest,
-        Map {
-            directions,
-            nodes: node_map,
-        },
-    ))
+fn nom_parse(input: &str) -> IResult<&str, (Vec<u8>, Vec<Node<'_>>)> {
+    let (rest, (directions, _, nodes)) = tuple((is_a("RL"), tag("\n\n"), lines(node)))(input)?;
+    Ok((rest, (directions.bytes().collect(), nodes)))
 }
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = Map<'a>;
 
-    fn parse_input(input: &'a str, test: bool) -> Self::Input {
-        let (_, map) = nom_parse(input).unwrap();
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let (directions, nodes) = parse::parse_all(input, nom_parse)?;
+        // nodes start on the third line
+        let error = |i: usize, column: usize, message: String| ParseError {
+            line: i + 3,
+            column,
+            message,
+        };
+        let mut node_map = HashMap::with_capacity(nodes.len());
+        for (i, node) in nodes.into_iter().enumerate() {
+            if node_map.contains_key(node.name) {
+                return Err(error(i, 1, format!("duplicate node '{}'", node.name)));
+            }
+            node_map.insert(node.name, (i, node));
+        }
+        // every referenced node has to exist, otherwise walking the map would fail
+        for (i, node) in node_map.values() {
+            let left_column = node.name.len() + 5;
+            let right_column = left_column + node.left.len() + 2;
+            for (next, column) in [(node.left, left_column), (node.right, right_column)] {
+                if !node_map.contains_key(next) {
+                    return Err(error(*i, column, format!("unknown node '{next}'")));
+                }
+            }
+        }
+        let map = Map {
+            directions,
+            nodes: node_map.into_iter().map(|(k, (_, v))| (k, v)).collect(),
+        };
         test_print!(test, "map: {map:#?}");
-        map
+        Ok(map)
+    }
+
+    const EXPLORE_HELP: &'static str =
+        "  walk <n> [start]             walk n steps from start (default AAA)";
+
+    fn explore(&self, map: &Self::Input, command: &str, args: &[&str]) -> Option<String> {
+        if command != "walk" {
+            return None;
+        }
+        let Some(Ok(steps)) = args.first().map(|n| n.parse::<usize>()) else {
+            return Some(String::from("usage: walk <n> [start]"));
+        };
+        let mut pos = args.get(1).copied().unwrap_or("AAA");
+        if !map.nodes.contains_key(pos) {
+            return Some(format!("unknown node '{pos}'"));
+        }
+        let mut z_hits = vec![];
+        for step in 0..steps {
+            pos = if map.directions[step % map.directions.len()] == b'L' {
+                map.nodes[pos].left
+            } else {
+                map.nodes[pos].right
+            };
+            if pos.ends_with('Z') {
+                z_hits.push(step + 1);
+            }
+        }
+        Some(format!(
+            "at {pos} after {steps} steps, nodes ending with Z reached after {z_hits:?} steps"
+        ))
     }
 
     fn solve1(&self, map: &Self::Input, _test: bool) -> String {
-        count_moves("AAA", map, |p| p == "ZZZ").to_string()
+        match count_moves("AAA", map, |p| p == "ZZZ") {
+            Some(moves) => moves.to_string(),
+            None => String::from("unreachable"),
+        }
     }
 
     // this solution assumes that the count of moves is the same after reaching the first Z
@@ -74,26 +125,41 @@ impl<'a> DaySolver<'a> for Solver {
         let pos = map.nodes.keys().filter(|k| k.ends_with('A'));
 
         // calculate moves per starting position
-        let move_counts = pos
+        let Some(move_counts) = pos
             .map(|p| count_moves(p, map, |p| p.ends_with('Z')))
-            .collect::<Vec<_>>();
+            .collect::<Option<Vec<_>>>()
+            .filter(|counts| !counts.is_empty())
+        else {
+            return String::from("unreachable");
+        };
 
         test_print!(test, "move counts: {move_counts:#?}");
 
         // find smallest common multiple
-        let mut steps = move_counts[0];
-        for count in move_counts.iter().skip(1) {
-            steps = num::integer::lcm(steps, *count);
+        match util::lcm(move_counts) {
+            Some(steps) => steps.to_string(),
+            None => String::from("overflow"),
         }
-
-        steps.to_string()
     }
 }
 
-fn count_moves(starting_pos: &str, map: &Map<'_>, end_check: impl Fn(&str) -> bool) -> usize {
+/// Returns None if the start does not exist or no end can be reached.
+fn count_moves(
+    starting_pos: &str,
+    map: &Map<'_>,
+    end_check: impl Fn(&str) -> bool,
+) -> Option<usize> {
+    if !map.nodes.contains_key(starting_pos) {
+        return None;
+    }
+    // after visiting every node at every direction index the walk repeats
+    let max_moves = map.nodes.len() * map.directions.len();
     let mut moves = 0;
     let mut pos = starting_pos;
     while !end_check(pos) {
+        if moves > max_moves {
+            return None;
+        }
         if map.directions[moves % map.directions.len()] == b'L' {
             pos = map.nodes[pos].left;
         } else {
@@ -101,5 +167,5 @@ fn count_moves(starting_pos: &str, map: &Map<'_>, end_check: impl Fn(&str) -> bo
         }
         moves += 1;
     }
-    moves
+    Some(moves)
 }
@@ -1,73 +1,65 @@
-use nom::{
-    character::complete::{self, newline, space1},
-    multi::separated_list1,
-    IResult,
+use crate::{
+    parse::{self, lines, numbers},
+    types::*,
 };
 
-use crate::types::*;
-
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Vec<i32>>;
 
-    fn parse_input(input: &'a str, test: bool) -> Self::Input {
-        let (_, list) = nom_parse(input).unwrap();
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let list = parse::parse_all(input, lines(numbers))?;
         test_print!(test, "list: {list:?}");
-        list
+        Ok(list)
     }
 
     fn solve1(&self, input: &Self::Input, test: bool) -> String {
-        let mut sum = 0;
-        for row in input {
-            let mut diff_sum = 0;
+        let sum = input.iter().try_fold(0i32, |sum, row| {
+            let mut diff_sum = 0i32;
             let mut diffs = row.to_owned();
             loop {
-                diffs = find_diffs(&diffs);
+                diffs = find_diffs(&diffs)?;
                 if diffs.iter().all(|&x| x == 0) {
                     break;
                 }
-                diff_sum += diffs[diffs.len() - 1];
+                diff_sum = diff_sum.checked_add(diffs[diffs.len() - 1])?;
             }
-            let prediction = row[row.len() - 1] + diff_sum;
+            let prediction = row[row.len() - 1].checked_add(diff_sum)?;
             test_print!(test, "prediction: {prediction}");
-            sum += prediction;
-        }
-        sum.to_string()
+            sum.checked_add(prediction)
+        });
+        sum.map_or(String::from("overflow"), |sum| sum.to_string())
     }
 
     fn solve2(&self, input: &Self::Input, test: bool) -> String {
-        let mut sum = 0;
-        for row in input {
+        let sum = input.iter().try_fold(0i32, |sum, row| {
             let mut diffs_stack = vec![row.to_owned()];
             loop {
-                let diffs = find_diffs(&diffs_stack[diffs_stack.len() - 1]);
+                let diffs = find_diffs(&diffs_stack[diffs_stack.len() - 1])?;
                 if diffs.iter().all(|&x| x == 0) {
                     break;
                 }
                 diffs_stack.push(diffs);
             }
-            let mut prediction = 0;
+            let mut prediction = 0i32;
             for diff in diffs_stack.iter().rev() {
-                prediction = diff[0] - prediction;
+                prediction = diff[0].checked_sub(prediction)?;
             }
             test_print!(test, "prediction: {prediction}");
-            sum += prediction;
-        }
-        sum.to_string()
+            sum.checked_add(prediction)
+        });
+        sum.map_or(String::from("overflow"), |sum| sum.to_string())
     }
 }
 
-fn find_diffs(row: &[i32]) -> Vec<i32> {
+/// None if a difference overflows.
+fn find_diffs(row: &[i32]) -> Option<Vec<i32>> {
     let mut last = row[0];
     let mut diffs = Vec::with_capacity(row.len() - 1);
     for d in row.iter().skip(1) {
-        diffs.push(d - last);
+        diffs.push(d.checked_sub(last)?);
         last = *d;
     }
-    diffs
-}
-
-fn nom_parse(input: &str) -> IResult<&str, Vec<Vec<i32>>> {
-    separated_list1(newline, separated_list1(space1, complete::i32))(input)
+    Some(diffs)
 }
@@ -1,13 +1,12 @@
-use std::{collections::HashMap, fmt::Display};
+use std::fmt::Display;
 
-use nom::{
-    character::complete::{newline, one_of},
-    multi::{many1, separated_list1},
-    IResult,
+use crate::{
+    geometry,
+    parse::{self, grid},
+    render::{self, Frame, Render, DARK, GREEN, YELLOW},
+    types::*,
 };
 
-use crate::types::*;
-
 pub struct Solver;
 
 #[derive(Debug, PartialEq, Copy, Clone)]
@@ -27,33 +26,35 @@ impl PipeSegment {
     /// Returns None if the pipe segment is not connected to the incoming direction.
     fn get_direction(&self, incoming: Direction) -> Option<Direction> {
         match self {
-            Self::Vertical if incoming.x == 0 => Some(Direction::new(0, incoming.y)),
-            Self::Horizontal if incoming.y == 0 => Some(Direction::new(incoming.x, 0)),
-            Self::NorthWest if incoming.x == 1 => Some(Direction::new(0, -1)),
-            Self::NorthWest if incoming.y == 1 => Some(Direction::new(-1, 0)),
-            Self::NorthEast if incoming.x == -1 => Some(Direction::new(0, -1)),
-            Self::NorthEast if incoming.y == 1 => Some(Direction::new(1, 0)),
-            Self::SouthWest if incoming.x == 1 => Some(Direction::new(0, 1)),
-            Self::SouthWest if incoming.y == -1 => Some(Direction::new(-1, 0)),
-            Self::SouthEast if incoming.x == -1 => Some(Direction::new(0, 1)),
-            Self::SouthEast if incoming.y == -1 => Some(Direction::new(1, 0)),
+            Self::Vertical if incoming.x == 0 => Some(incoming),
+            Self::Horizontal if incoming.y == 0 => Some(incoming),
+            Self::NorthWest if incoming == Direction::RIGHT => Some(Direction::UP),
+            Self::NorthWest if incoming == Direction::DOWN => Some(Direction::LEFT),
+            Self::NorthEast if incoming == Direction::LEFT => Some(Direction::UP),
+            Self::NorthEast if incoming == Direction::DOWN => Some(Direction::RIGHT),
+            Self::SouthWest if incoming == Direction::RIGHT => Some(Direction::DOWN),
+            Self::SouthWest if incoming == Direction::UP => Some(Direction::LEFT),
+            Self::SouthEast if incoming == Direction::LEFT => Some(Direction::DOWN),
+            Self::SouthEast if incoming == Direction::UP => Some(Direction::RIGHT),
             _ => None,
         }
     }
 }
 
-impl From<char> for PipeSegment {
-    fn from(value: char) -> Self {
+impl TryFrom<char> for PipeSegment {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
         match value {
-            '|' => Self::Vertical,
-            '-' => Self::Horizontal,
-            'L' => Self::NorthEast,
-            'J' => Self::NorthWest,
-            '7' => Self::SouthWest,
-            'F' => Self::SouthEast,
-            '.' => Self::Ground,
-            'S' => Self::Start,
-            c => panic!("invalid pipe character: '{c}'"),
+            '|' => Ok(Self::Vertical),
+            '-' => Ok(Self::Horizontal),
+            'L' => Ok(Self::NorthEast),
+            'J' => Ok(Self::NorthWest),
+            '7' => Ok(Self::SouthWest),
+            'F' => Ok(Self::SouthEast),
+            '.' => Ok(Self::Ground),
+            'S' => Ok(Self::Start),
+            c => Err(c),
         }
     }
 }
@@ -75,11 +76,10 @@ impl Display for PipeSegment {
 }
 
 impl<'a> DaySolver<'a> for Solver {
-    type Input = HashMap<Position, PipeSegment>;
-
-    fn parse_input(input: &'a str, test: bool) -> Self::Input {
-        let (_, segments) = nom_parse(input).unwrap();
+    type Input = Vec<Position>;
 
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let segments = parse::parse_all(input, grid(|c| PipeSegment::try_from(c).ok()))?;
         find_loop(test, segments)
     }
 
@@ -87,137 +87,92 @@ impl<'a> DaySolver<'a> for Solver {
         (path.len() / 2).to_string()
     }
 
-    fn solve2(&self, path: &Self::Input, test: bool) -> String {
-        if test {
-            print!("Path: [");
-            for pos in path.keys() {
-                print!("{}, ", pos);
-            }
-            println!("]");
+    fn solve2(&self, path: &Self::Input, _test: bool) -> String {
+        if render::enabled() {
+            render::finish("day10", &Loop(path));
         }
-        // find outermost positions
-        let mut min_x = i64::MAX;
-        let mut min_y = i64::MAX;
-        let mut max_x = i64::MIN;
-        let mut max_y = i64::MIN;
-        for pos in path.keys() {
-            if pos.x < min_x {
-                min_x = pos.x;
-            }
-            if pos.y < min_y {
-                min_y = pos.y;
-            }
-            if pos.x > max_x {
-                max_x = pos.x;
-            }
-            if pos.y > max_y {
-                max_y = pos.y;
+        // every tile of the loop is a vertex, so the enclosed tiles are the interior lattice points
+        geometry::interior_points(path).to_string()
+    }
+}
+
+struct Loop<'a>(&'a [Position]);
+
+impl Render for Loop<'_> {
+    fn render(&self) -> Frame {
+        let width = self.0.iter().map(|p| p.x).max().unwrap_or(0) as usize + 1;
+        let height = self.0.iter().map(|p| p.y).max().unwrap_or(0) as usize + 1;
+        // loop tiles, and whether they connect to the tile above
+        let mut tiles = vec![vec![None; width]; height];
+        for (i, a) in self.0.iter().enumerate() {
+            let b = self.0[(i + 1) % self.0.len()];
+            let (ax, ay) = (a.x as usize, a.y as usize);
+            let (bx, by) = (b.x as usize, b.y as usize);
+            tiles[ay][ax].get_or_insert(false);
+            tiles[by][bx].get_or_insert(false);
+            if ax == bx && ay.abs_diff(by) == 1 {
+                tiles[ay.max(by)][ax] = Some(true);
             }
         }
-        let mut enclosed = 0;
-        for y in min_y..=max_y {
+        let mut frame = Frame::new(width, height);
+        for (y, row) in tiles.iter().enumerate() {
+            // scanline, crossing a tile connected upwards switches between inside and outside
             let mut inside = false;
-            for x in min_x..=max_x {
-                if let Some(&segment) = path.get(&Position::new(x, y)) {
-                    if segment == PipeSegment::Vertical
-                        || segment == PipeSegment::NorthEast
-                        || segment == PipeSegment::NorthWest
-                    {
-                        inside = !inside;
+            for (x, tile) in row.iter().enumerate() {
+                match tile {
+                    Some(up) => {
+                        inside ^= up;
+                        frame.set(x, y, '#', YELLOW);
                     }
-                } else if inside {
-                    enclosed += 1;
+                    None if inside => frame.set(x, y, 'I', GREEN),
+                    None => frame.set(x, y, '.', DARK),
                 }
             }
         }
-        // check if enclosed by walls for each position
-        enclosed.to_string()
+        frame
     }
 }
 
-fn find_loop(test: bool, input: Vec<Vec<PipeSegment>>) -> HashMap<Position, PipeSegment> {
+/// Follow the loop from the starting position and return its tiles in order.
+fn find_loop(test: bool, input: Vec<Vec<PipeSegment>>) -> Result<Vec<Position>, ParseError> {
     test_print!(test, "{input:?}]");
+    let error = |pos: Position, message: &str| ParseError {
+        line: pos.y as usize + 1,
+        column: pos.x as usize + 1,
+        message: message.into(),
+    };
     // find starting position
-    let mut pos1 = Position::new(0, 0);
-    let mut pos2 = Position::new(0, 0);
-    for (y, row) in input.iter().enumerate() {
-        for (x, segment) in row.iter().enumerate() {
-            if *segment == PipeSegment::Start {
-                pos1 = Position::new(x as i32, y as i32);
-                pos2 = Position::new(x as i32, y as i32);
-            }
-        }
-    }
-    let mut path = HashMap::new();
-    // check where tiles are connected to the starting position
-    // and move there to start
-    let mut dir1 = Direction::new(0, 0);
-    let mut dir2 = Direction::new(0, 0);
-    let start_x = pos1.x;
-    let start_y = pos1.y;
-    let mut start_dir = (0, 0, 0, 0);
-    for &(dir_x, dir_y) in &[(0, -1), (0, 1), (1, 0), (-1, 0)] {
-        let x = start_x + dir_x;
-        let y = start_y + dir_y;
-        if x < 0 || y < 0 || x >= input[0].len() as i64 || y >= input.len() as i64 {
-            continue;
-        }
-        let segment = input[y as usize][x as usize];
-        if let Some(dir) = segment.get_direction(Direction::new(dir_x, dir_y)) {
-            if dir1 == (0, 0) {
-                pos1 = Position::new(x, y);
-                dir1 = dir;
-                start_dir.0 = dir_x;
-                start_dir.1 = dir_y;
-                path.insert(pos1, segment);
-            } else {
-                pos2 = Position::new(x, y);
-                dir2 = dir;
-                start_dir.2 = dir_x;
-                start_dir.3 = dir_y;
-                path.insert(pos2, segment);
-                break;
-            }
+    let start = input
+        .iter()
+        .enumerate()
+        .find_map(|(y, row)| {
+            let x = row.iter().position(|s| *s == PipeSegment::Start)?;
+            Position::from_index(x, y)
+        })
+        .ok_or_else(|| error(Position::new(0, 0), "no starting position"))?;
+    // check which tile is connected to the starting position and move there to start
+    let (mut pos, mut dir) = start
+        .neighbors()
+        .into_iter()
+        .find_map(|next| {
+            let dir = next.get(&input)?.get_direction(next - start)?;
+            Some((next, dir))
+        })
+        .ok_or_else(|| error(start, "starting position is not connected to a pipe"))?;
+    test_print!(test, "Starting: pos: {pos:?}, dir: {dir:?}");
+    let mut path = vec![start];
+    // follow the loop back to the start
+    loop {
+        test_print!(test, "pos: {pos} {dir}");
+        path.push(pos);
+        pos += dir;
+        if pos == start {
+            break;
         }
+        dir = pos
+            .get(&input)
+            .and_then(|segment| segment.get_direction(dir))
+            .ok_or_else(|| error(pos, "pipe loop is not closed"))?;
     }
-    test_print!(
-        test,
-        "Starting:\n pos1: {pos1:?}, dir1: {dir1:?}\n pos2: {pos2:?}, dir1: {dir2:?}\n"
-    );
-    let start_segment = match start_dir {
-        (0, -1, 0, 1) => PipeSegment::Vertical,
-        (0, -1, -1, 0) => PipeSegment::NorthWest,
-        (0, -1, 1, 0) => PipeSegment::NorthEast,
-        (0, 1, 1, 0) => PipeSegment::SouthEast,
-        (0, 1, -1, 0) => PipeSegment::SouthWest,
-        (1, 0, -1, 0) => PipeSegment::Horizontal,
-        _ => panic!("invalid starting direction"),
-    };
-    // add Starting segment
-    path.insert(Position::new(start_x, start_y), start_segment);
-    // find loop
-    while pos1 != pos2 {
-        test_print!(test, "pos1: {pos1} {dir1}; pos2: {pos2:?} {dir2}");
-        pos1 += dir1;
-        let segment = input[pos1.y as usize][pos1.x as usize];
-        dir1 = segment.get_direction(dir1).unwrap();
-        path.insert(pos1, segment);
-        pos2 += dir2;
-        let segment = input[pos2.y as usize][pos2.x as usize];
-        dir2 = input[pos2.y as usize][pos2.x as usize]
-            .get_direction(dir2)
-            .unwrap();
-        path.insert(pos2, segment);
-    }
-    path
-}
-
-fn nom_parse(input: &str) -> IResult<&str, Vec<Vec<PipeSegment>>> {
-    separated_list1(newline, many1(pipe_segment))(input)
-}
-
-fn pipe_segment(input: &str) -> IResult<&str, PipeSegment> {
-    let (rest, segment) = one_of("|-LJ7F.S")(input)?;
-    let segment = PipeSegment::from(segment);
-    Ok((rest, segment))
+    Ok(path)
 }
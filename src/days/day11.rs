@@ -1,24 +1,22 @@
-use crate::types::*;
+use crate::{
+    parse::{self, grid},
+    types::*,
+};
 
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Vec<bool>>;
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        let mut map = Vec::new();
-        for line in input.lines() {
-            let row = line
-                .chars()
-                .map(|c| match c {
-                    '.' => false,
-                    '#' => true,
-                    _ => panic!("invalid input"),
-                })
-                .collect::<Vec<_>>();
-            map.push(row);
-        }
-        map
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        parse::parse_all(
+            input,
+            grid(|c| match c {
+                '.' => Some(false),
+                '#' => Some(true),
+                _ => None,
+            }),
+        )
     }
 
     fn solve1(&self, map: &Self::Input, test: bool) -> String {
@@ -49,28 +47,21 @@ impl<'a> DaySolver<'a> for Solver {
 fn expand(map: &[Vec<bool>], expand: usize) -> Vec<Position> {
     let mut empty_columns = Vec::new();
     for x in 0..map[0].len() {
-        let mut empty = true;
-        for row in map {
-            if row[x] {
-                empty = false;
-                break;
-            }
-        }
-        if empty {
+        if map.iter().all(|row| !row[x]) {
             empty_columns.push(x);
         }
     }
     let mut empty_rows = 0;
     let mut galaxies = Vec::new();
-    for y in 0..map.len() {
+    for (y, row) in map.iter().enumerate() {
         let mut empty = true;
-        for x in 0..map[0].len() {
-            if map[y][x] {
+        for (x, &galaxy) in row.iter().enumerate() {
+            if galaxy {
                 empty = false;
                 let y = y + empty_rows;
                 let columns = empty_columns.iter().filter(|&&c| c < x).count();
                 let x = x + columns * expand - columns;
-                galaxies.push(Position::new(x as i32, y as i32));
+                galaxies.push(Position::from_index(x, y).unwrap());
             }
         }
         if empty {
@@ -1,24 +1,28 @@
 // solution partly taken from https://github.com/ageron/aoc2023-rust/blob/main/src/day12.rs
 
 use nom::{
-    bytes::complete::is_not,
-    character::complete::{self, newline},
+    bytes::complete::is_a,
+    character::complete,
+    combinator::{map, verify},
     multi::separated_list1,
     sequence::tuple,
     IResult,
 };
 
-use crate::types::*;
+use crate::{
+    parse::{self, lines, number},
+    types::*,
+};
 
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Row>;
 
-    fn parse_input(input: &'a str, test: bool) -> Self::Input {
-        let (_, rows) = nom_parse(input).unwrap();
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let rows = parse::parse_all(input, lines(row))?;
         test_print!(test, "rows: {:#?}", rows);
-        rows
+        Ok(rows)
     }
 
     fn solve1(&self, rows: &Self::Input, _test: bool) -> String {
@@ -40,17 +44,12 @@ pub struct Row {
     groups: Vec<usize>,
 }
 
-fn nom_parse(input: &str) -> IResult<&str, Vec<Row>> {
-    separated_list1(newline, row)(input)
-}
-
 fn row(input: &str) -> IResult<&str, Row> {
     let (rest, (line, _, groups)) = tuple((
-        is_not(" "),
+        is_a("?.#"),
         complete::char(' '),
-        separated_list1(complete::char(','), complete::u8),
+        separated_list1(complete::char(','), group),
     ))(input)?;
-    let groups = groups.into_iter().map(|g| g as usize).collect();
     Ok((
         rest,
         Row {
@@ -60,6 +59,11 @@ fn row(input: &str) -> IResult<&str, Row> {
     ))
 }
 
+/// size of a group of damaged springs, can't be empty
+fn group(input: &str) -> IResult<&str, usize> {
+    map(verify(number::<u8>, |&n| n > 0), usize::from)(input)
+}
+
 fn possible_solutions(row: &Row) -> usize {
     possible_solutions_rec(row.line.as_bytes(), &row.groups)
 }
@@ -1,24 +1,28 @@
-use crate::types::*;
+use crate::{
+    parse::{self, blocks, grid},
+    types::*,
+};
 
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Vec<Vec<char>>>;
 
-    fn parse_input(input: &'a str, test: bool) -> Self::Input {
-        let groups = input
-            .split("\n\n")
-            .map(|g| g.lines().map(|l| l.chars().collect()).collect())
-            .collect();
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let groups = parse::parse_all(input, blocks(grid(|c| "#.".contains(c).then_some(c))))?;
         test_print!(test, "groups: {:#?}", groups);
-        groups
+        Ok(groups)
     }
 
     fn solve1(&self, groups: &Self::Input, test: bool) -> String {
         groups
             .iter()
             .fold(0, |acc, g| {
-                let (x, y) = mirror_line(g, 0);
+                // patterns without a reflection line don't add anything
+                let Some((x, y)) = mirror_line(g, 0) else {
+                    test_print!(test, "no reflection found");
+                    return acc;
+                };
                 test_print!(test, "mirrored at ({}, {})", x, y);
                 acc + x + y * 100
             })
@@ -29,15 +33,43 @@ impl<'a> DaySolver<'a> for Solver {
         groups
             .iter()
             .fold(0, |acc, g| {
-                let (x, y) = mirror_line(g, 1);
+                // patterns without a reflection line don't add anything
+                let Some((x, y)) = mirror_line(g, 1) else {
+                    test_print!(test, "no reflection found");
+                    return acc;
+                };
                 test_print!(test, "mirrored at ({}, {})", x, y);
                 acc + x + y * 100
             })
             .to_string()
     }
+
+    fn explain(&self, groups: &Self::Input) -> Option<Explanation> {
+        let rows = groups.iter().enumerate().map(|(i, g)| {
+            vec![
+                (i + 1).to_string(),
+                format!("{}x{}", g[0].len(), g.len()),
+                describe(mirror_line(g, 0)),
+                describe(mirror_line(g, 1)),
+            ]
+        });
+        Some(Explanation::default().section(
+            "reflection lines, part 2 fixes exactly one smudge",
+            &["pattern", "size", "part 1", "part 2"],
+            rows.collect(),
+        ))
+    }
 }
 
-fn mirror_line(g: &[Vec<char>], errors: usize) -> (u32, u32) {
+fn describe(line: Option<(u32, u32)>) -> String {
+    match line {
+        Some((0, y)) => format!("horizontal below row {y}"),
+        Some((x, _)) => format!("vertical right of column {x}"),
+        None => String::from("none"),
+    }
+}
+
+fn mirror_line(g: &[Vec<char>], errors: usize) -> Option<(u32, u32)> {
     // check for horizontal reflection
     'rows: for y in 0..g.len() - 1 {
         let mut smudges = 0;
@@ -54,7 +86,7 @@ fn mirror_line(g: &[Vec<char>], errors: usize) -> (u32, u32) {
         if smudges != errors {
             continue 'rows;
         }
-        return (0, (y + 1) as u32);
+        return Some((0, (y + 1) as u32));
     }
     // check for vertical reflection
     'cols: for x in 0..g[0].len() - 1 {
@@ -72,12 +104,7 @@ fn mirror_line(g: &[Vec<char>], errors: usize) -> (u32, u32) {
         if smudges != errors {
             continue 'cols;
         }
-        return ((x + 1) as u32, 0);
-    }
-    let mut pattern = String::new();
-    for row in g {
-        pattern.push_str(&row.iter().collect::<String>());
-        pattern.push('\n');
+        return Some(((x + 1) as u32, 0));
     }
-    panic!("no symmetry found in pattern:\n{}", pattern);
+    None
 }
@@ -1,52 +1,60 @@
 use std::{
-    collections::{hash_map::DefaultHasher, HashMap},
+    collections::hash_map::DefaultHasher,
     hash::{Hash, Hasher},
 };
 
-use crate::types::*;
+use crate::{
+    parse::{self, grid},
+    render::{self, Frame, Render, BLUE, DARK, GRAY},
+    types::*,
+    util::CycleFinder,
+};
 
 pub struct Solver;
 
+struct Platform<'a>(&'a [Vec<char>]);
+
+impl Render for Platform<'_> {
+    fn render(&self) -> Frame {
+        Frame::from_grid(self.0, |&c| match c {
+            'O' => ('O', BLUE),
+            '#' => ('#', GRAY),
+            c => (c, DARK),
+        })
+    }
+}
+
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Vec<char>>;
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        input.lines().map(|l| l.chars().collect()).collect()
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        parse::parse_all(input, grid(|c| "O#.".contains(c).then_some(c)))
     }
 
-    fn solve1(&self, map: &Self::Input, test: bool) -> String {
+    fn solve1(&self, map: &Self::Input, _test: bool) -> String {
         // roll rocks north
         let map = map.clone();
         let map = roll(map.clone(), (0, -1));
         let load = count_load(&map);
-        if test {
-            println!("map after rolling:");
-            for row in &map {
-                println!("{}", row.iter().collect::<String>());
-            }
-        }
+        render::finish("day14_1", &Platform(&map));
         // add load for rocks on top
         load.to_string()
     }
 
     fn solve2(&self, input: &Self::Input, _test: bool) -> String {
         let mut map = input.clone();
-        let mut hashed_maps = HashMap::new();
+        let mut finder = CycleFinder::new();
         let mut cycles = 0;
         loop {
             for dir in &[(0, -1), (-1, 0), (0, 1), (1, 0)] {
                 map = roll(map, *dir);
             }
             cycles += 1;
+            render::step("day14_2", cycles, &Platform(&map));
             let mut hasher = DefaultHasher::new();
             map.hash(&mut hasher);
-            let hash = hasher.finish();
-            let hashed_at = hashed_maps.entry(hash).or_insert(cycles);
-            if *hashed_at < cycles {
-                // found a cycle
-                let cycle_len = cycles - *hashed_at;
-                let remaining = (1000000000 - cycles) % cycle_len;
-                for _ in 0..remaining {
+            if let Some(cycle) = finder.visit(hasher.finish(), cycles) {
+                for _ in 0..cycle.remaining(cycles, 1000000000) {
                     for dir in &[(0, -1), (-1, 0), (0, 1), (1, 0)] {
                         map = roll(map, *dir);
                     }
@@ -54,6 +62,7 @@ impl<'a> DaySolver<'a> for Solver {
                 break;
             }
         }
+        render::finish("day14_2", &Platform(&map));
         count_load(&map).to_string()
     }
 }
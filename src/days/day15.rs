@@ -1,18 +1,36 @@
 use std::collections::{hash_map::Entry, HashMap};
 
-use nom::{bytes::complete::is_not, character::complete, multi::separated_list1, IResult};
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::{self, alpha1},
+    combinator::recognize,
+    multi::separated_list1,
+    sequence::{pair, preceded},
+    IResult,
+};
 
-use crate::types::*;
+use crate::{
+    parse::{self, number},
+    types::*,
+    util::{Normalize, TrailingNewline},
+};
 
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<&'a str>;
 
-    fn parse_input(input: &'a str, test: bool) -> Self::Input {
-        let (_, parsed) = nom_parse(input).unwrap();
+    // the sequence is a single line, a newline would become part of the last step
+    const NORMALIZE: Normalize = Normalize {
+        trailing_newline: TrailingNewline::Strip,
+        ..Normalize::DEFAULT
+    };
+
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let parsed = parse::parse_all(input, separated_list1(complete::char(','), step))?;
         test_print!(test, "{:?}", parsed);
-        parsed
+        Ok(parsed)
     }
 
     fn solve1(&self, input: &Self::Input, test: bool) -> String {
@@ -56,8 +74,13 @@ impl<'a> DaySolver<'a> for Solver {
     }
 }
 
-fn nom_parse(input: &str) -> IResult<&str, Vec<&str>> {
-    separated_list1(complete::char(','), is_not(","))(input)
+/// a step like `rn=1` or `cm-`, kept as text for hashing
+fn step(input: &str) -> IResult<&str, &str> {
+    let operation = alt((
+        tag("-"),
+        preceded(complete::char('='), recognize(number::<u8>)),
+    ));
+    recognize(pair(alpha1, operation))(input)
 }
 
 fn hash(input: &str) -> u8 {
@@ -2,19 +2,31 @@ use std::collections::HashMap;
 
 use rayon::prelude::*;
 
-use crate::types::*;
+use crate::{
+    parse::{self, grid},
+    render::{self, Frame, Render, GRAY, RED, YELLOW},
+    types::*,
+};
 
 pub struct Solver;
 
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Vec<u8>>;
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        input.lines().map(|l| l.as_bytes().to_vec()).collect()
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        parse::parse_all(input, grid(|c| "./\\|-".contains(c).then_some(c as u8)))
     }
 
     fn solve1(&self, input: &Self::Input, _test: bool) -> String {
-        count_energized_tiles(Position::new(0, 0), Direction::new(1, 0), input).to_string()
+        let energized = energized_tiles(Position::new(0, 0), Direction::RIGHT, input);
+        render::finish(
+            "day16",
+            &Beams {
+                grid: input,
+                energized: &energized,
+            },
+        );
+        energized.len().to_string()
     }
 
     fn solve2(&self, input: &Self::Input, test: bool) -> String {
@@ -24,13 +36,10 @@ impl<'a> DaySolver<'a> for Solver {
             .map(|&y| {
                 let mut max = 0;
                 for x in 0..input[0].len() {
+                    let pos = Position::from_index(x, y).unwrap();
                     if y == 0 {
                         //  check going down
-                        let count = count_energized_tiles(
-                            Position::new(x as i32, y as i32),
-                            Direction::new(0, 1),
-                            input,
-                        );
+                        let count = count_energized_tiles(pos, Direction::DOWN, input);
                         if count > max {
                             max = count;
                             test_print!(test, "best_starting pos: ({x},{y})");
@@ -38,11 +47,7 @@ impl<'a> DaySolver<'a> for Solver {
                     }
                     if x == 0 {
                         // check going right
-                        let count = count_energized_tiles(
-                            Position::new(x as i32, y as i32),
-                            Direction::new(1, 0),
-                            input,
-                        );
+                        let count = count_energized_tiles(pos, Direction::RIGHT, input);
                         if count > max {
                             max = count;
                             test_print!(test, "best_starting pos: ({x},{y})");
@@ -50,11 +55,7 @@ impl<'a> DaySolver<'a> for Solver {
                     }
                     if x == input[0].len() - 1 {
                         // check going left
-                        let count = count_energized_tiles(
-                            Position::new(x as i32, y as i32),
-                            Direction::new(-1, 0),
-                            input,
-                        );
+                        let count = count_energized_tiles(pos, Direction::LEFT, input);
                         if count > max {
                             max = count;
                             test_print!(test, "best_starting pos: ({x},{y})");
@@ -62,11 +63,7 @@ impl<'a> DaySolver<'a> for Solver {
                     }
                     if y == input.len() - 1 {
                         // check going up
-                        let count = count_energized_tiles(
-                            Position::new(x as i32, y as i32),
-                            Direction::new(0, -1),
-                            input,
-                        );
+                        let count = count_energized_tiles(pos, Direction::UP, input);
                         if count > max {
                             max = count;
                             test_print!(test, "best_starting pos: ({x},{y})");
@@ -81,7 +78,35 @@ impl<'a> DaySolver<'a> for Solver {
     }
 }
 
+struct Beams<'a> {
+    grid: &'a [Vec<u8>],
+    energized: &'a HashMap<Position, Direction>,
+}
+
+impl Render for Beams<'_> {
+    fn render(&self) -> Frame {
+        let mut frame = Frame::from_grid(self.grid, |&b| (b as char, GRAY));
+        for (pos, dir) in self.energized {
+            let (x, y) = pos.to_index().unwrap();
+            match self.grid[y][x] {
+                b'.' => frame.set(x, y, dir.to_string().chars().next().unwrap(), YELLOW),
+                b => frame.set(x, y, b as char, RED),
+            }
+        }
+        frame
+    }
+}
+
 fn count_energized_tiles(start_pos: Position, start_dir: Direction, input: &[Vec<u8>]) -> usize {
+    energized_tiles(start_pos, start_dir, input).len()
+}
+
+/// Follow the beams and return the last direction of a beam through every energized tile.
+fn energized_tiles(
+    start_pos: Position,
+    start_dir: Direction,
+    input: &[Vec<u8>],
+) -> HashMap<Position, Direction> {
     let mut positions = vec![start_pos];
     let mut directions = vec![start_dir];
     let mut energized = HashMap::new();
@@ -89,11 +114,7 @@ fn count_energized_tiles(start_pos: Position, start_dir: Direction, input: &[Vec
         // get last beam in queue
         let mut dir = directions.pop().unwrap();
         // follow bean until reaching existing beam or leaving the grid
-        loop {
-            // stop if out of grid
-            if pos.x >= input[0].len() as i64 || pos.y >= input.len() as i64 {
-                break;
-            }
+        while let Some(&tile) = pos.get(input) {
             // stop if beam already energized
             if let Some(energized_dir) = energized.get(&pos) {
                 if energized_dir == &dir {
@@ -102,32 +123,28 @@ fn count_energized_tiles(start_pos: Position, start_dir: Direction, input: &[Vec
             }
             energized.insert(pos, dir);
             // split or redirect beam
-            dir = match input[pos.y as usize][pos.x as usize] {
+            dir = match tile {
                 b'/' => Direction::new(-dir.y, -dir.x),
                 b'\\' => Direction::new(dir.y, dir.x),
                 b'|' if dir.x != 0 => {
                     // add new beam going down
-                    positions.push(pos + Direction::new(0, 1));
-                    directions.push(Direction::new(0, 1));
+                    positions.push(pos + Direction::DOWN);
+                    directions.push(Direction::DOWN);
                     // continue with beam going up
-                    Direction::new(0, -1)
+                    Direction::UP
                 }
                 b'-' if dir.y != 0 => {
                     // add new beam going right
-                    positions.push(pos + Direction::new(1, 0));
-                    directions.push(Direction::new(1, 0));
+                    positions.push(pos + Direction::RIGHT);
+                    directions.push(Direction::RIGHT);
                     // continue with beam going left
-                    Direction::new(-1, 0)
+                    Direction::LEFT
                 }
                 _ => dir,
             };
-            // stop if at edge and heading out
-            if pos.x == 0 && dir.x == -1 || pos.y == 0 && dir.y == -1 {
-                break;
-            }
             // move beam
             pos += dir;
         }
     }
-    energized.len()
+    energized
 }
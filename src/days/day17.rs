@@ -3,10 +3,16 @@ use std::{
     rc::Rc,
 };
 
-use crate::types::*;
+use crate::{
+    parse::{self, grid},
+    render::{self, Frame, Render, BLUE, GRAY, YELLOW},
+    types::*,
+};
 
 pub struct Solver;
 
+type Visited = HashMap<(usize, usize, u8, u8), Rc<Node>>;
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 struct Node {
     g: usize,
@@ -34,91 +40,89 @@ impl PartialOrd for Node {
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Vec<u8>>;
 
-    fn parse_input(input: &'a str, _test: bool) -> Self::Input {
-        input
-            .lines()
-            .map(|l| l.as_bytes().iter().map(|b| b - b'0').collect())
-            .collect()
+    fn parse_input(input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+        parse::parse_all(input, grid(|c| c.to_digit(10).map(|d| d as u8)))
     }
 
-    fn solve1(&self, grid: &Self::Input, test: bool) -> String {
-        let (end, visited) = find_path(grid, false, test);
-        if test {
-            print_path(Some(end.clone()), grid, &visited);
-        }
-        end.g.to_string()
+    fn solve1(&self, grid: &Self::Input, _test: bool) -> String {
+        let (end, visited) = find_path(grid, false);
+        let search = Search {
+            grid,
+            visited: &visited,
+            end: end.clone(),
+        };
+        render::finish("day17_1", &search);
+        end.map_or(String::from("unreachable"), |end| end.g.to_string())
     }
 
-    fn solve2(&self, grid: &Self::Input, test: bool) -> String {
-        let (end, visited) = find_path(grid, true, test);
-        if test {
-            print_path(Some(end.clone()), grid, &visited);
-        }
-        end.g.to_string()
+    fn solve2(&self, grid: &Self::Input, _test: bool) -> String {
+        let (end, visited) = find_path(grid, true);
+        let search = Search {
+            grid,
+            visited: &visited,
+            end: end.clone(),
+        };
+        render::finish("day17_2", &search);
+        end.map_or(String::from("unreachable"), |end| end.g.to_string())
     }
 }
 
-fn print_path(
+struct Search<'a> {
+    grid: &'a [Vec<u8>],
+    visited: &'a Visited,
     end: Option<Rc<Node>>,
-    grid: &[Vec<u8>],
-    visited: &HashMap<(usize, usize, u8, u8), Rc<Node>>,
-) {
-    let mut path = vec![];
-    if let Some(end) = end {
-        path.push(end.pos);
-        let mut current = end;
-        while let Some(node) = current.prev.clone() {
-            path.push(node.pos);
-            current = node;
+}
+
+impl Render for Search<'_> {
+    fn render(&self) -> Frame {
+        let mut frame = Frame::from_grid(self.grid, |cost| {
+            (char::from_digit(*cost as u32, 10).unwrap(), GRAY)
+        });
+        for &(x, y, _, _) in self.visited.keys() {
+            frame.set(x, y, '#', BLUE);
         }
-        path.reverse();
-    }
-    let visited = visited
-        .keys()
-        .map(|&(x, y, _, _)| (x, y))
-        .collect::<Vec<_>>();
-    print!("{esc}[2J{esc}[1;1H", esc = 27 as char);
-    for (y, row) in grid.iter().enumerate() {
-        for (x, cost) in row.iter().enumerate() {
-            if path.contains(&(x, y)) {
-                print!("O")
-            } else if visited.contains(&(x, y)) {
-                print!("#")
-            } else {
-                print!("{cost}")
-            }
+        let mut current = self.end.clone();
+        while let Some(node) = current {
+            frame.set(node.pos.0, node.pos.1, 'O', YELLOW);
+            current = node.prev.clone();
         }
-        println!()
+        frame
     }
 }
 
-fn find_path(
-    grid: &[Vec<u8>],
-    part_two: bool,
-    test: bool,
-) -> (Rc<Node>, HashMap<(usize, usize, u8, u8), Rc<Node>>) {
+/// Returns the end node of the cheapest path, if there is one, and all visited states.
+fn find_path(grid: &[Vec<u8>], part_two: bool) -> (Option<Rc<Node>>, Visited) {
     let mut queue = BinaryHeap::new();
-    // let mut queue = Vec::new();
+    // every step costs at least the cheapest block, so the estimate never overshoots
+    let min_cost = grid.iter().flatten().min().copied().unwrap_or(0) as usize;
+    let end = (grid[0].len() - 1, grid.len() - 1);
+    let estimate = |(x, y): (usize, usize)| (end.0 - x + end.1 - y) * min_cost;
     let start = Rc::new(Node {
         g: 0,
-        h: (grid.len() + grid[0].len()),
+        h: estimate((0, 0)),
         pos: (0, 0),
         prev: None,
         last_dir: 0,
         same_steps: 0,
     });
     queue.push(start.clone());
-    let mut visited: HashMap<(usize, usize, u8, u8), Rc<Node>> = HashMap::new();
+    let mut visited: Visited = HashMap::new();
     let max_steps = if part_two { 10 } else { 3 };
+    let name = if part_two { "day17_2" } else { "day17_1" };
+    let mut steps = 0;
     while let Some(current) = queue.pop() {
-        if test {
-            print_path(None, grid, &visited);
+        steps += 1;
+        if render::enabled() {
+            let search = Search {
+                grid,
+                visited: &visited,
+                end: None,
+            };
+            render::step(name, steps, &search);
         }
         // check if goal reached
-        if current.pos == (grid[0].len() - 1, grid.len() - 1)
-            && (!part_two || current.same_steps >= 4)
-        {
-            return (current, visited);
+        if current.pos == end && (!part_two || current.same_steps >= 4) {
+            return (Some(current), visited);
         }
         let key = (
             current.pos.0,
@@ -135,8 +139,10 @@ fn find_path(
         visited.insert(key, current.clone());
         // add neighbors
         for dir in 0..4 {
+            // the start has no direction yet
+            let first = current.prev.is_none();
             // cant go back
-            if dir == (current.last_dir + 2) % 4 {
+            if dir == (current.last_dir + 2) % 4 && !first {
                 continue;
             }
             // cant go same dir more than n times
@@ -144,11 +150,7 @@ fn find_path(
                 continue;
             }
             // part two can only turn after 4 moves
-            if part_two
-                && current.same_steps < 4
-                && dir != current.last_dir
-                && current.pos != (0, 0)
-            {
+            if part_two && current.same_steps < 4 && dir != current.last_dir && !first {
                 continue;
             }
             let next_pos = match dir {
@@ -159,8 +161,8 @@ fn find_path(
                 _ => continue,
             };
             let next_g = current.g + grid[next_pos.1][next_pos.0] as usize;
-            let next_h = (grid.len() - next_pos.1 + grid[0].len() - next_pos.0) * 2;
-            let next_steps = if dir == current.last_dir {
+            let next_h = estimate(next_pos);
+            let next_steps = if dir == current.last_dir && !first {
                 current.same_steps + 1
             } else {
                 1
@@ -177,5 +179,5 @@ fn find_path(
             queue.push(node.clone());
         }
     }
-    panic!("no path found");
+    (None, visited)
 }
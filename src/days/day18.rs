@@ -1,24 +1,24 @@
-use std::collections::HashSet;
-
 use nom::{
-    bytes::complete::tag,
-    character::complete::{self, alphanumeric1, newline, one_of, space1},
-    multi::separated_list1,
-    sequence::{delimited, tuple},
+    bytes::complete::{tag, take_while_m_n},
+    character::complete::{self, one_of, space1},
+    sequence::{delimited, pair, tuple},
     IResult,
 };
 
-use crate::types::*;
+use crate::{
+    geometry,
+    parse::{self, lines, number},
+    types::*,
+};
 
 pub struct Solver;
 impl<'a> DaySolver<'a> for Solver {
     type Input = Vec<Step>;
 
-    fn parse_input(input: &'a str, test: bool) -> Self::Input {
-        let (rem, parsed) = nom_parse(input).unwrap();
-        test_print!(test, "rem: {rem}");
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let parsed = parse::parse_all(input, lines(step))?;
         test_print!(test, "{parsed:?}");
-        parsed
+        Ok(parsed)
     }
 
     fn solve1(&self, plan: &Self::Input, _test: bool) -> String {
@@ -40,46 +40,37 @@ impl<'a> DaySolver<'a> for Solver {
     }
 }
 
-// calculate area of given hole
-// fixed some errors by looking at https://github.com/Tom-the-Bomb/aoc-2023/blob/main/aoc-py/solutions/day18.py
+// calculate area of given hole including the dug out border
 fn calculate_area(steps: Vec<(Direction, u32)>) -> i64 {
     let mut position = Position::new(0, 0);
-    let mut vertices = vec![];
-    vertices.push(position);
-    let mut border = 0;
+    let mut vertices = Vec::with_capacity(steps.len());
     for (dir, dist) in steps {
-        position += dir * dist;
-        border += dist;
         vertices.push(position);
+        position += dir * dist;
     }
-    // compute area using shoelace formula for inner polygon area
-    let mut area = 0;
-    for a in 0..vertices.len() {
-        let b = (a + 1) % vertices.len();
-        area += vertices[a].x * vertices[b].y - vertices[a].y * vertices[b].x
-    }
-    area /= 2;
-    // adding on the border
-    area + border as i64 / 2 + 1
-}
-
-fn nom_parse(input: &str) -> IResult<&str, Vec<Step>> {
-    separated_list1(newline, step)(input)
+    geometry::enclosed_points(&vertices)
 }
 
 fn step(input: &str) -> IResult<&str, Step> {
     let (remaining, (dir, _, distance_1, _, hex_code)) = tuple((
         one_of("URDL"),
         space1,
-        complete::u8,
+        number,
         space1,
-        delimited(tag("(#"), alphanumeric1, complete::char(')')),
+        delimited(
+            tag("(#"),
+            pair(
+                take_while_m_n(5, 5, |c: char| c.is_ascii_hexdigit()),
+                one_of("0123"),
+            ),
+            complete::char(')'),
+        ),
     ))(input)?;
     let direction_1 = match dir {
-        'U' => Direction::new(0, -1),
-        'R' => Direction::new(1, 0),
-        'D' => Direction::new(0, 1),
-        'L' => Direction::new(-1, 0),
+        'U' => Direction::UP,
+        'R' => Direction::RIGHT,
+        'D' => Direction::DOWN,
+        'L' => Direction::LEFT,
         _ => unreachable!(),
     };
     let (direction_2, distance_2) = parse_hex_code(hex_code);
@@ -94,13 +85,12 @@ fn step(input: &str) -> IResult<&str, Step> {
     ))
 }
 
-fn parse_hex_code(hex_code: &str) -> (Direction, u32) {
-    let (dist, dir) = hex_code.split_at(5);
+fn parse_hex_code((dist, dir): (&str, char)) -> (Direction, u32) {
     let direction = match dir {
-        "0" => Direction::new(1, 0),
-        "1" => Direction::new(0, 1),
-        "2" => Direction::new(-1, 0),
-        "3" => Direction::new(0, -1),
+        '0' => Direction::RIGHT,
+        '1' => Direction::DOWN,
+        '2' => Direction::LEFT,
+        '3' => Direction::UP,
         _ => unreachable!(),
     };
     let distance = u32::from_str_radix(dist, 16).unwrap();
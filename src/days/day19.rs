@@ -1,107 +1,260 @@
 use std::collections::HashMap;
 
 use nom::{
-    bytes::complete::{is_not, tag},
-    character::complete::{self, alphanumeric1, newline},
-    multi::separated_list1,
-    sequence::{delimited, tuple},
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, char, one_of},
+    combinator::{map, recognize},
+    multi::{many0, separated_list1},
+    sequence::{delimited, pair, separated_pair, terminated, tuple},
     IResult,
 };
 
-use crate::types::*;
+use crate::{
+    parse::{self, key_value, lines, number},
+    types::*,
+};
 
 pub struct Solver;
 impl<'a> DaySolver<'a> for Solver {
     type Input = (HashMap<String, Workflow>, Vec<Part>);
 
-    fn parse_input(input: &'a str, test: bool) -> Self::Input {
-        let (input_workflows, input_parts) = input.split_once("\n\n").unwrap();
-        let (_, workflows) = parse_workflows(input_workflows).unwrap();
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let (workflows, parts) = parse::parse_all(
+            input,
+            separated_pair(lines(named_workflow), tag("\n\n"), lines(part)),
+        )?;
         test_print!(test, "workflows: {workflows:?}");
-        let (_, parts) = parse_parts(input_parts).unwrap();
         test_print!(test, "parts: {parts:?}");
         let mut workflow_map = HashMap::new();
         for (k, v) in workflows {
             workflow_map.insert(String::from(k), v);
         }
-        (workflow_map, parts)
+        validate(input, &workflow_map)?;
+        Ok((workflow_map, parts))
     }
 
-    fn solve1(&self, input: &Self::Input, test: bool) -> String {
+    fn solve1(&self, input: &Self::Input, _test: bool) -> String {
         let (workflows, parts) = input;
-        let mut accepted = vec![];
-        for part in parts {
-            let mut w = String::from("in");
-            while w != "R" {
-                w = workflows[&w].process(part);
-                if w == "A" {
-                    accepted.push(part);
-                    break;
-                }
-            }
-        }
-        let sum = accepted.iter().map(|p| p.x + p.m + p.a + p.s).sum::<u32>();
+        let accepted = parts
+            .iter()
+            .filter(|part| workflow_path(workflows, part).last().unwrap() == "A");
+        let sum = accepted.map(Part::rating).sum::<u64>();
         sum.to_string()
     }
 
     fn solve2(&self, input: &Self::Input, test: bool) -> String {
-        todo!()
+        let (workflows, _) = input;
+        let all = [(1, 4000); 4];
+        accepted_combinations(workflows, "in", all, test).to_string()
+    }
+
+    fn explain(&self, input: &Self::Input) -> Option<Explanation> {
+        let (workflows, parts) = input;
+        let rows = parts.iter().filter_map(|part| {
+            let path = workflow_path(workflows, part);
+            (path.last().unwrap() == "A").then(|| {
+                vec![
+                    format!("{{x={},m={},a={},s={}}}", part.x, part.m, part.a, part.s),
+                    path.join(" -> "),
+                    part.rating().to_string(),
+                ]
+            })
+        });
+        Some(Explanation::default().section(
+            "accepted parts",
+            &["part", "workflows", "rating"],
+            rows.collect(),
+        ))
+    }
+
+    const EXPLORE_HELP: &'static str =
+        "  part {x=..,m=..,a=..,s=..}   show the workflows a part passes through";
+
+    fn explore(&self, input: &Self::Input, command: &str, args: &[&str]) -> Option<String> {
+        match command {
+            "part" => Some(match parse::parse_all(&args.concat(), part) {
+                Ok(p) => workflow_path(&input.0, &p).join(" -> "),
+                Err(e) => e.to_string(),
+            }),
+            _ => None,
+        }
     }
 }
 
-fn parse_parts(input: &str) -> IResult<&str, Vec<Part>> {
-    separated_list1(newline, part)(input)
+/// Names of all workflows the part passes through, ending with A or R.
+fn workflow_path(workflows: &HashMap<String, Workflow>, part: &Part) -> Vec<String> {
+    let mut path = vec![String::from("in")];
+    while !matches!(path.last().unwrap().as_str(), "A" | "R") {
+        let next = workflows[path.last().unwrap()].process(part);
+        path.push(next);
+    }
+    path
 }
-fn part(input: &str) -> IResult<&str, Part> {
-    let (rem, (_, x, _, m, _, a, _, s, _)) = tuple((
-        tag("{x="),
-        complete::u32,
-        tag(",m="),
-        complete::u32,
-        tag(",a="),
-        complete::u32,
-        tag(",s="),
-        complete::u32,
-        tag("}"),
-    ))(input)?;
-    Ok((rem, Part { x, m, a, s }))
-}
-
-fn parse_workflows(input: &str) -> IResult<&str, Vec<(&str, Workflow)>> {
-    separated_list1(
-        newline,
-        tuple((alphanumeric1, delimited(tag("{"), workflow, tag("}")))),
-    )(input)
+
+/// Inclusive bounds of the x, m, a and s ratings.
+type Ranges = [(u32, u32); 4];
+
+/// Number of rating combinations within the ranges that the workflows accept, starting at the
+/// named one. The ranges are split at every condition, the matching part goes to the
+/// destination of the check and the rest continues with the next one.
+fn accepted_combinations(
+    workflows: &HashMap<String, Workflow>,
+    start: &str,
+    ranges: Ranges,
+    test: bool,
+) -> u64 {
+    let mut sum = 0;
+    // ranges still to be sent through a workflow, a stack keeps long chains off the call stack
+    let mut todo = vec![(start, ranges)];
+    'todo: while let Some((name, mut ranges)) = todo.pop() {
+        match name {
+            "A" => {
+                test_print!(test, "accepted {ranges:?}");
+                sum += ranges
+                    .iter()
+                    .map(|&(lo, hi)| u64::from(hi - lo + 1))
+                    .product::<u64>();
+                continue;
+            }
+            "R" => continue,
+            _ => {}
+        }
+        let workflow = &workflows[name];
+        for c in &workflow.checks {
+            let i = c.field.index();
+            let (lo, hi) = ranges[i];
+            let (matching, rest) = match c.cond {
+                Condition::Lower => ((lo, hi.min(c.val.saturating_sub(1))), (lo.max(c.val), hi)),
+                Condition::Higher => ((lo.max(c.val.saturating_add(1)), hi), (lo, hi.min(c.val))),
+            };
+            if matching.0 <= matching.1 {
+                let mut split = ranges;
+                split[i] = matching;
+                todo.push((&c.dest, split));
+            }
+            if rest.0 > rest.1 {
+                continue 'todo;
+            }
+            ranges[i] = rest;
+        }
+        todo.push((&workflow.default, ranges));
+    }
+    sum
 }
-fn workflow(input: &str) -> IResult<&str, Workflow> {
-    let (rest, wf) = is_not("}")(input)?;
-    let mut default = String::from("");
-    let mut checks = vec![];
-    for part in wf.split(',') {
-        if let Some((cond, dest)) = part.split_once(':') {
-            // condition case
-            if let Some((field, val)) = cond.split_once('<') {
-                checks.push(Check {
-                    cond: Condition::Lower,
-                    field: Field::from(field),
-                    val: val.parse().unwrap(),
-                    dest: String::from(dest),
-                })
+
+/// Check that all destinations exist and every part ends in A or R.
+fn validate(input: &str, workflows: &HashMap<String, Workflow>) -> Result<(), ParseError> {
+    let error = |name: &str, message: String| ParseError {
+        line: input
+            .lines()
+            .position(|l| l.starts_with(&format!("{name}{{")))
+            .map_or(1, |l| l + 1),
+        column: 1,
+        message,
+    };
+    if !workflows.contains_key("in") {
+        return Err(error("in", String::from("missing workflow 'in'")));
+    }
+    for (name, workflow) in workflows {
+        for dest in workflow.destinations() {
+            if !matches!(dest, "A" | "R") && !workflows.contains_key(dest) {
+                return Err(error(name, format!("unknown workflow '{dest}'")));
             }
-            if let Some((field, val)) = cond.split_once('>') {
-                checks.push(Check {
-                    cond: Condition::Higher,
-                    field: Field::from(field),
-                    val: val.parse().unwrap(),
-                    dest: String::from(dest),
-                })
+        }
+    }
+    if let Some(name) = find_cycle("in", workflows) {
+        return Err(error(name, format!("workflow '{name}' is part of a cycle")));
+    }
+    Ok(())
+}
+
+/// Depth first search for a workflow that can be reached from itself, with an explicit stack
+/// so that long chains of workflows can't overflow the call stack.
+fn find_cycle<'w>(start: &'w str, workflows: &'w HashMap<String, Workflow>) -> Option<&'w str> {
+    // false while the workflow is on the stack
+    let mut finished = HashMap::from([(start, false)]);
+    let mut stack = vec![(start, workflows.get(start)?.destinations())];
+    while let Some((name, destinations)) = stack.last_mut() {
+        let Some(dest) = destinations.next() else {
+            finished.insert(*name, true);
+            stack.pop();
+            continue;
+        };
+        match finished.get(dest) {
+            Some(true) => {}
+            Some(false) => return Some(dest),
+            None => {
+                if let Some(workflow) = workflows.get(dest) {
+                    finished.insert(dest, false);
+                    stack.push((dest, workflow.destinations()));
+                }
             }
-        } else {
-            // default case
-            default = String::from(part);
         }
     }
-    Ok((rest, Workflow { checks, default }))
+    None
+}
+
+fn field(input: &str) -> IResult<&str, Field> {
+    map(recognize(one_of("xmas")), Field::from)(input)
+}
+
+fn part(input: &str) -> IResult<&str, Part> {
+    let (rem, values) = delimited(
+        char('{'),
+        separated_list1(char(','), key_value(field, '=', number)),
+        char('}'),
+    )(input)?;
+    let mut part = Part {
+        x: 0,
+        m: 0,
+        a: 0,
+        s: 0,
+    };
+    for (field, val) in values {
+        match field {
+            Field::X => part.x = val,
+            Field::M => part.m = val,
+            Field::A => part.a = val,
+            Field::S => part.s = val,
+        }
+    }
+    Ok((rem, part))
+}
+
+fn named_workflow(input: &str) -> IResult<&str, (&str, Workflow)> {
+    tuple((alphanumeric1, delimited(tag("{"), workflow, tag("}"))))(input)
+}
+
+/// e.g. `a<2006:qkq,m>2090:A,rfg`
+fn workflow(input: &str) -> IResult<&str, Workflow> {
+    let (rest, (checks, default)) =
+        pair(many0(terminated(check, char(','))), alphanumeric1)(input)?;
+    Ok((
+        rest,
+        Workflow {
+            checks,
+            default: String::from(default),
+        },
+    ))
+}
+
+fn check(input: &str) -> IResult<&str, Check> {
+    let (rest, (field, cond, val, _, dest)) =
+        tuple((field, one_of("<>"), number, char(':'), alphanumeric1))(input)?;
+    let cond = if cond == '<' {
+        Condition::Lower
+    } else {
+        Condition::Higher
+    };
+    Ok((
+        rest,
+        Check {
+            cond,
+            field,
+            val,
+            dest: String::from(dest),
+        },
+    ))
 }
 
 #[derive(Debug)]
@@ -116,6 +269,16 @@ enum Field {
     A,
     S,
 }
+impl Field {
+    fn index(&self) -> usize {
+        match self {
+            Self::X => 0,
+            Self::M => 1,
+            Self::A => 2,
+            Self::S => 3,
+        }
+    }
+}
 impl From<&str> for Field {
     fn from(value: &str) -> Self {
         match value {
@@ -134,6 +297,13 @@ pub struct Workflow {
     default: String,
 }
 impl Workflow {
+    fn destinations(&self) -> impl Iterator<Item = &str> {
+        self.checks
+            .iter()
+            .map(|c| c.dest.as_str())
+            .chain([self.default.as_str()])
+    }
+
     fn process(&self, part: &Part) -> String {
         for c in &self.checks {
             let success = match c.cond {
@@ -165,6 +335,11 @@ pub struct Part {
 }
 
 impl Part {
+    /// Sum of the four ratings, which can be larger than a single one.
+    fn rating(&self) -> u64 {
+        [self.x, self.m, self.a, self.s].map(u64::from).iter().sum()
+    }
+
     fn get_val(&self, f: &Field) -> u32 {
         match f {
             Field::X => self.x,
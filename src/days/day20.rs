@@ -0,0 +1,294 @@
+use std::collections::{HashMap, VecDeque};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, one_of},
+    combinator::opt,
+    multi::separated_list1,
+    sequence::{pair, separated_pair},
+    IResult,
+};
+
+use crate::{
+    parse::{self, lines},
+    types::*,
+    util::{self, CycleFinder},
+};
+
+/// Part 2 gives up after this many presses.
+const MAX_PRESSES: usize = 100_000;
+/// A press gives up after this many pulses, loops in the network can pulse forever.
+const MAX_PULSES: usize = 100_000;
+
+pub struct Solver;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Broadcaster,
+    FlipFlop,
+    Conjunction,
+    /// only receives pulses, e.g. `output` or `rx`
+    Sink,
+}
+
+#[derive(Debug)]
+struct Module<'a> {
+    name: &'a str,
+    kind: Kind,
+    /// destination and the input slot of this module at the destination
+    outputs: Vec<(usize, usize)>,
+    inputs: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub struct Network<'a> {
+    modules: Vec<Module<'a>>,
+    broadcaster: usize,
+}
+
+/// Flip-flop states and the last pulse a conjunction received on every input.
+struct State {
+    on: Vec<bool>,
+    memory: Vec<Vec<bool>>,
+    high_inputs: Vec<usize>,
+}
+
+impl<'a> DaySolver<'a> for Solver {
+    type Input = Network<'a>;
+
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let definitions = parse::parse_all(input, lines(module))?;
+        let network = network(definitions)?;
+        test_print!(test, "network: {network:#?}");
+        Ok(network)
+    }
+
+    fn solve1(&self, network: &Self::Input, _test: bool) -> String {
+        let mut state = State::new(network);
+        let mut counts = [0u64; 2];
+        for _ in 0..1000 {
+            if !network.press(&mut state, |_, _, high| counts[usize::from(high)] += 1) {
+                return String::from("unbounded");
+            }
+        }
+        (counts[0] * counts[1]).to_string()
+    }
+
+    // this solution assumes that rx is fed by a single conjunction, whose inputs are high once
+    // every cycle starting with the first press, like in the real input. Other networks are
+    // simulated until rx receives a low pulse.
+    fn solve2(&self, network: &Self::Input, test: bool) -> String {
+        let Some(rx) = network.find("rx") else {
+            return String::from("unreachable");
+        };
+        let mut feeder = match network.modules[rx].inputs[..] {
+            [feeder] if network.modules[feeder].kind == Kind::Conjunction => Some(feeder),
+            _ => None,
+        };
+        let watched = feeder.map_or(&[][..], |f| &network.modules[f].inputs[..]);
+        // an input of the feeder is in the same state whenever it sends a high pulse
+        let mut finders = watched
+            .iter()
+            .map(|_| CycleFinder::new())
+            .collect::<Vec<_>>();
+        let mut cycles = vec![None; watched.len()];
+        let mut state = State::new(network);
+        for presses in 1..=MAX_PRESSES {
+            let mut reached = false;
+            let bounded = network.press(&mut state, |from, to, high| {
+                if to == rx && !high {
+                    reached = true;
+                }
+                if Some(to) == feeder && high {
+                    let i = watched.iter().position(|&w| w == from).unwrap();
+                    if cycles[i].is_none() {
+                        // further high pulses of the same press don't start a new cycle
+                        cycles[i] = finders[i].visit((), presses).filter(|c| c.len > 0);
+                    }
+                }
+            });
+            if reached {
+                return presses.to_string();
+            }
+            if !bounded {
+                return String::from("unbounded");
+            }
+            if !cycles.is_empty() && cycles.iter().all(Option::is_some) {
+                test_print!(test, "cycles of high pulses to the feeder: {cycles:?}");
+                let lens = cycles
+                    .iter()
+                    .flatten()
+                    .map(|c| (c.start == c.len).then_some(c.len));
+                if let Some(lens) = lens.collect::<Option<Vec<_>>>() {
+                    return util::lcm(lens).map_or(String::from("overflow"), |p| p.to_string());
+                }
+                // the cycles don't start with the first press, keep simulating
+                feeder = None;
+                cycles.clear();
+            }
+        }
+        String::from("unreachable")
+    }
+
+    const EXPLORE_HELP: &'static str =
+        "  dot [file]                   export the network as Graphviz, printed without a file";
+
+    fn explore(&self, network: &Self::Input, command: &str, args: &[&str]) -> Option<String> {
+        if command != "dot" {
+            return None;
+        }
+        let dot = network.to_dot();
+        Some(match args.first() {
+            Some(path) => match std::fs::write(path, dot) {
+                Ok(()) => format!("wrote {path}"),
+                Err(e) => format!("could not write {path}: {e}"),
+            },
+            None => dot,
+        })
+    }
+}
+
+/// e.g. `%a -> inv, con`
+fn module(input: &str) -> IResult<&str, (Option<char>, &str, Vec<&str>)> {
+    let (rest, ((prefix, name), outputs)) = separated_pair(
+        pair(opt(one_of("%&")), alpha1),
+        tag(" -> "),
+        separated_list1(tag(", "), alpha1),
+    )(input)?;
+    Ok((rest, (prefix, name, outputs)))
+}
+
+fn network<'a>(
+    definitions: Vec<(Option<char>, &'a str, Vec<&'a str>)>,
+) -> Result<Network<'a>, ParseError> {
+    let error = |i: usize, message: String| ParseError {
+        line: i + 1,
+        column: 1,
+        message,
+    };
+    let mut indices = HashMap::new();
+    let mut modules = vec![];
+    for (i, (prefix, name, _)) in definitions.iter().enumerate() {
+        let kind = match prefix {
+            Some('%') => Kind::FlipFlop,
+            Some(_) => Kind::Conjunction,
+            None if *name == "broadcaster" => Kind::Broadcaster,
+            None => return Err(error(i, format!("module '{name}' has no type"))),
+        };
+        if indices.insert(*name, modules.len()).is_some() {
+            return Err(error(i, format!("duplicate module '{name}'")));
+        }
+        modules.push(Module {
+            name,
+            kind,
+            outputs: vec![],
+            inputs: vec![],
+        });
+    }
+    let Some(&broadcaster) = indices.get("broadcaster") else {
+        return Err(error(0, String::from("missing module 'broadcaster'")));
+    };
+    for (from, (_, _, outputs)) in definitions.into_iter().enumerate() {
+        for name in outputs {
+            let to = *indices.entry(name).or_insert_with(|| {
+                modules.push(Module {
+                    name,
+                    kind: Kind::Sink,
+                    outputs: vec![],
+                    inputs: vec![],
+                });
+                modules.len() - 1
+            });
+            let slot = modules[to].inputs.len();
+            modules[to].inputs.push(from);
+            modules[from].outputs.push((to, slot));
+        }
+    }
+    Ok(Network {
+        modules,
+        broadcaster,
+    })
+}
+
+impl State {
+    fn new(network: &Network<'_>) -> Self {
+        let modules = &network.modules;
+        Self {
+            on: vec![false; modules.len()],
+            memory: modules
+                .iter()
+                .map(|m| vec![false; m.inputs.len()])
+                .collect(),
+            high_inputs: vec![0; modules.len()],
+        }
+    }
+}
+
+impl Network<'_> {
+    fn find(&self, name: &str) -> Option<usize> {
+        self.modules.iter().position(|m| m.name == name)
+    }
+
+    /// Press the button once and call `pulse` with the sender, receiver and level of every
+    /// pulse, including the one from the button. False if the pulses didn't stop after
+    /// [`MAX_PULSES`].
+    fn press(&self, state: &mut State, mut pulse: impl FnMut(usize, usize, bool)) -> bool {
+        // the button is not a module, it shows up as the broadcaster sending to itself
+        let mut queue = VecDeque::from([(self.broadcaster, self.broadcaster, 0, false)]);
+        let mut pulses = 0;
+        while let Some((from, to, slot, high)) = queue.pop_front() {
+            pulses += 1;
+            if pulses > MAX_PULSES {
+                return false;
+            }
+            pulse(from, to, high);
+            let module = &self.modules[to];
+            let send = match module.kind {
+                Kind::Broadcaster => high,
+                Kind::FlipFlop if high => continue,
+                Kind::FlipFlop => {
+                    state.on[to] = !state.on[to];
+                    state.on[to]
+                }
+                Kind::Conjunction => {
+                    if state.memory[to][slot] != high {
+                        state.memory[to][slot] = high;
+                        if high {
+                            state.high_inputs[to] += 1;
+                        } else {
+                            state.high_inputs[to] -= 1;
+                        }
+                    }
+                    state.high_inputs[to] != module.inputs.len()
+                }
+                Kind::Sink => continue,
+            };
+            for &(next, next_slot) in &module.outputs {
+                queue.push_back((to, next, next_slot, send));
+            }
+        }
+        true
+    }
+
+    /// Graphviz source with flip-flops as boxes and conjunctions as diamonds.
+    fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph modules {\n");
+        for module in &self.modules {
+            let shape = match module.kind {
+                Kind::Broadcaster => "doublecircle",
+                Kind::FlipFlop => "box",
+                Kind::Conjunction => "diamond",
+                Kind::Sink => "circle",
+            };
+            dot += &format!("    \"{}\" [shape={shape}];\n", module.name);
+        }
+        for module in &self.modules {
+            for &(to, _) in &module.outputs {
+                let to = self.modules[to].name;
+                dot += &format!("    \"{}\" -> \"{to}\";\n", module.name);
+            }
+        }
+        dot += "}\n";
+        dot
+    }
+}
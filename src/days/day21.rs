@@ -0,0 +1,182 @@
+use std::collections::HashSet;
+
+use crate::{
+    parse::{self, grid},
+    types::*,
+};
+
+const STEPS1: usize = 64;
+const STEPS2: usize = 26_501_365;
+/// Part 2 gives up if the growth isn't quadratic after walking this many map repeats.
+const MAX_REPEATS: usize = 12;
+
+pub struct Solver;
+
+#[derive(Debug)]
+pub struct Garden {
+    rocks: Vec<Vec<bool>>,
+    start: Position,
+}
+
+impl<'a> DaySolver<'a> for Solver {
+    type Input = Garden;
+
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let cells = parse::parse_all(input, grid(|c| "#.S".contains(c).then_some(c)))?;
+        let mut starts = cells.iter().enumerate().flat_map(|(y, row)| {
+            let starts = row.iter().enumerate().filter(|(_, &c)| c == 'S');
+            starts.map(move |(x, _)| Position::from_index(x, y).unwrap())
+        });
+        let error = |pos: Position, message: &str| ParseError {
+            line: pos.y as usize + 1,
+            column: pos.x as usize + 1,
+            message: String::from(message),
+        };
+        let start = starts
+            .next()
+            .ok_or_else(|| error(Position::new(0, 0), "no starting position"))?;
+        if let Some(pos) = starts.next() {
+            return Err(error(pos, "more than one starting position"));
+        }
+        let rocks = cells
+            .iter()
+            .map(|row| row.iter().map(|&c| c == '#').collect())
+            .collect();
+        let garden = Garden { rocks, start };
+        test_print!(test, "start: {}", garden.start);
+        Ok(garden)
+    }
+
+    fn solve1(&self, garden: &Self::Input, _test: bool) -> String {
+        Walk::new(garden, false).reachable(STEPS1).to_string()
+    }
+
+    fn solve2(&self, garden: &Self::Input, test: bool) -> String {
+        match extrapolate(garden, STEPS2, test) {
+            Some(plots) => plots.to_string(),
+            None => String::from("no quadratic growth"),
+        }
+    }
+
+    const EXPLORE_HELP: &'static str =
+        "  reach <steps> [brute]        plots reachable on the infinite map, optionally brute forced";
+
+    fn explore(&self, garden: &Self::Input, command: &str, args: &[&str]) -> Option<String> {
+        if command != "reach" {
+            return None;
+        }
+        let Some(Ok(steps)) = args.first().map(|n| n.parse::<usize>()) else {
+            return Some(String::from("usage: reach <steps> [brute]"));
+        };
+        let plots = if args.get(1) == Some(&"brute") {
+            Some(Walk::new(garden, true).reachable(steps))
+        } else {
+            extrapolate(garden, steps, false)
+        };
+        Some(match plots {
+            Some(plots) => format!("{plots} plots reachable in exactly {steps} steps"),
+            None => String::from("no quadratic growth on this map, try brute force"),
+        })
+    }
+}
+
+/// Breadth first search from the start, one layer per step.
+struct Walk<'g> {
+    garden: &'g Garden,
+    /// whether the map repeats in every direction
+    infinite: bool,
+    seen: HashSet<Position>,
+    frontier: Vec<Position>,
+    /// plots reachable in exactly as many steps as the index, the walk can go back and forth,
+    /// so these are the plots of the same parity found up to then
+    reachable: Vec<usize>,
+}
+
+impl<'g> Walk<'g> {
+    fn new(garden: &'g Garden, infinite: bool) -> Self {
+        Self {
+            garden,
+            infinite,
+            seen: HashSet::from([garden.start]),
+            frontier: vec![garden.start],
+            reachable: vec![1],
+        }
+    }
+
+    fn is_plot(&self, pos: Position) -> bool {
+        let rocks = &self.garden.rocks;
+        let pos = if self.infinite {
+            let (width, height) = (rocks[0].len() as i64, rocks.len() as i64);
+            Position::new(pos.x.rem_euclid(width), pos.y.rem_euclid(height))
+        } else {
+            pos
+        };
+        pos.get(rocks) == Some(&false)
+    }
+
+    fn reachable(&mut self, steps: usize) -> usize {
+        while self.reachable.len() <= steps {
+            let mut next = vec![];
+            for pos in &self.frontier {
+                for neighbor in pos.neighbors() {
+                    if self.is_plot(neighbor) && !self.seen.contains(&neighbor) {
+                        self.seen.insert(neighbor);
+                        next.push(neighbor);
+                    }
+                }
+            }
+            let step = self.reachable.len();
+            // going back and forth needs a neighbor, a walled in start can't move at all
+            let before = if step >= 2 && self.reachable[1] > 0 {
+                self.reachable[step - 2]
+            } else {
+                0
+            };
+            self.reachable.push(before + next.len());
+            self.frontier = next;
+        }
+        self.reachable[steps]
+    }
+}
+
+/// Plots reachable on the infinite map. Once the walk covers whole repeats of the map, the
+/// count grows quadratically with the number of repeats, so it is measured at the same offset
+/// into consecutive repeats until the second differences settle, then extrapolated. Only
+/// square maps are supported like the real input, others would repeat too slowly.
+fn extrapolate(garden: &Garden, steps: usize, test: bool) -> Option<usize> {
+    let period = garden.rocks.len();
+    if garden.rocks[0].len() != period {
+        return None;
+    }
+    let offset = steps % period;
+    let mut walk = Walk::new(garden, true);
+    let mut counts = vec![];
+    for repeats in 0..=MAX_REPEATS {
+        let walked = offset + repeats * period;
+        let count = walk.reachable(walked);
+        if walked == steps {
+            return Some(count);
+        }
+        counts.push(count as i64);
+        let Some(last) = counts.len().checked_sub(5) else {
+            continue;
+        };
+        // three equal second differences
+        let second = |i: usize| counts[i + 2] - 2 * counts[i + 1] + counts[i];
+        if second(last) != second(last + 1) || second(last) != second(last + 2) {
+            continue;
+        }
+        test_print!(
+            test,
+            "quadratic after {last} repeats of {period} steps: {counts:?}"
+        );
+        let n = ((steps - offset) / period - last) as i128;
+        let (first, diff) = (
+            counts[last] as i128,
+            (counts[last + 1] - counts[last]) as i128,
+        );
+        let plots = first + n * diff + n * (n - 1) / 2 * second(last) as i128;
+        return usize::try_from(plots).ok();
+    }
+    None
+}
@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+
+use nom::{
+    character::complete::char,
+    combinator::map,
+    sequence::{separated_pair, tuple},
+    IResult,
+};
+
+use crate::{
+    parse::{self, lines, number},
+    render::{self, Backend, Frame, Render, BLUE, GRAY, GREEN, RED, YELLOW},
+    types::*,
+};
+
+/// Bricks covering more cells than this are rejected, the real ones cover at most a few.
+const MAX_FOOTPRINT: i64 = 10_000;
+
+pub struct Solver;
+
+#[derive(Debug, Clone, Copy)]
+struct Brick {
+    /// the corner with the lowest coordinates
+    low: Position3,
+    high: Position3,
+}
+
+/// The settled bricks from the bottom up, so supporting bricks come first.
+#[derive(Debug)]
+pub struct Stack {
+    bricks: Vec<Brick>,
+    supported_by: Vec<Vec<usize>>,
+    supports: Vec<Vec<usize>>,
+}
+
+impl<'a> DaySolver<'a> for Solver {
+    type Input = Stack;
+
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let bricks = parse::parse_all(input, lines(brick))?;
+        let error = |i: usize, message: &str| ParseError {
+            line: i + 1,
+            column: 1,
+            message: String::from(message),
+        };
+        if let Some(i) = bricks.iter().position(|b| b.low.z < 1) {
+            return Err(error(i, "brick below the ground"));
+        }
+        if let Some(i) = bricks.iter().position(|b| b.low.x < 0 || b.low.y < 0) {
+            return Err(error(i, "negative coordinate"));
+        }
+        let too_large = |b: &Brick| {
+            let (width, depth) = (b.high.x - b.low.x, b.high.y - b.low.y);
+            width >= MAX_FOOTPRINT
+                || depth >= MAX_FOOTPRINT
+                || (width + 1) * (depth + 1) > MAX_FOOTPRINT
+        };
+        if let Some(i) = bricks.iter().position(too_large) {
+            return Err(error(i, "brick too large"));
+        }
+        let stack = settle(bricks);
+        test_print!(test, "supported by: {:?}", stack.supported_by);
+        Ok(stack)
+    }
+
+    fn solve1(&self, stack: &Self::Input, _test: bool) -> String {
+        render::finish("day22", stack);
+        // a brick can go if everything on it rests on another brick too
+        let safe = stack.supports.iter().filter(|supported| {
+            supported
+                .iter()
+                .all(|&above| stack.supported_by[above].len() > 1)
+        });
+        safe.count().to_string()
+    }
+
+    fn solve2(&self, stack: &Self::Input, test: bool) -> String {
+        let falls = (0..stack.bricks.len()).map(|i| {
+            let count = stack.chain_reaction(i);
+            test_print!(test, "removing brick {i} makes {count} bricks fall");
+            count
+        });
+        falls.sum::<usize>().to_string()
+    }
+
+    const EXPLORE_HELP: &'static str =
+        "  side                         show the settled stack from the front and the side";
+
+    fn explore(&self, stack: &Self::Input, command: &str, _args: &[&str]) -> Option<String> {
+        if command != "side" {
+            return None;
+        }
+        let mut out = vec![];
+        Backend::Ascii.write(&stack.render(), &mut out).unwrap();
+        Some(String::from_utf8(out).unwrap())
+    }
+}
+
+fn position(input: &str) -> IResult<&str, Position3> {
+    map(
+        tuple((number, char(','), number, char(','), number)),
+        |(x, _, y, _, z)| Position3::new(x, y, z),
+    )(input)
+}
+
+/// e.g. `1,0,1~1,2,1`
+fn brick(input: &str) -> IResult<&str, Brick> {
+    map(separated_pair(position, char('~'), position), |(a, b)| {
+        Brick {
+            low: Position3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z)),
+            high: Position3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z)),
+        }
+    })(input)
+}
+
+impl Brick {
+    fn footprint(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        (self.low.x..=self.high.x)
+            .flat_map(move |x| (self.low.y..=self.high.y).map(move |y| (x, y)))
+    }
+}
+
+/// Let the bricks fall from the lowest one up, tracking the top brick of every column.
+fn settle(mut bricks: Vec<Brick>) -> Stack {
+    bricks.sort_by_key(|b| b.low.z);
+    let mut tops = HashMap::<(i64, i64), (i64, usize)>::new();
+    let mut supported_by = vec![vec![]; bricks.len()];
+    for (i, brick) in bricks.iter_mut().enumerate() {
+        let below = brick
+            .footprint()
+            .filter_map(|column| tops.get(&column).copied())
+            .collect::<Vec<_>>();
+        let floor = below.iter().map(|&(z, _)| z).max().unwrap_or(0);
+        let below = below.into_iter().filter(|&(z, _)| z == floor);
+        let mut supporters = below.map(|(_, j)| j).collect::<Vec<_>>();
+        supporters.sort_unstable();
+        supporters.dedup();
+        supported_by[i] = supporters;
+        let drop = brick.low.z - floor - 1;
+        brick.low.z -= drop;
+        brick.high.z -= drop;
+        for column in brick.footprint() {
+            tops.insert(column, (brick.high.z, i));
+        }
+    }
+    let mut supports = vec![vec![]; bricks.len()];
+    for (i, below) in supported_by.iter().enumerate() {
+        for &j in below {
+            supports[j].push(i);
+        }
+    }
+    Stack {
+        bricks,
+        supported_by,
+        supports,
+    }
+}
+
+impl Stack {
+    /// Number of other bricks that fall when the brick is removed.
+    fn chain_reaction(&self, removed: usize) -> usize {
+        let mut fallen = vec![false; self.bricks.len()];
+        fallen[removed] = true;
+        let mut count = 0;
+        // bricks only rest on earlier ones, so a single pass in settling order is enough
+        for i in removed + 1..self.bricks.len() {
+            let below = &self.supported_by[i];
+            if !below.is_empty() && below.iter().all(|&j| fallen[j]) {
+                fallen[i] = true;
+                count += 1;
+            }
+        }
+        count
+    }
+}
+
+impl Render for Stack {
+    /// Side views along the y axis and along the x axis next to each other, bricks are lettered
+    /// in settling order and `?` marks several bricks behind each other.
+    fn render(&self) -> Frame {
+        const COLORS: [render::Color; 4] = [RED, GREEN, BLUE, YELLOW];
+        let max = |axis: fn(&Brick) -> i64| self.bricks.iter().map(axis).max().unwrap_or(0);
+        let (width, depth, height) = (max(|b| b.high.x), max(|b| b.high.y), max(|b| b.high.z));
+        let (width, depth, height) = (width as usize + 1, depth as usize + 1, height as usize);
+        let mut frame = Frame::new(width + 3 + depth, height + 1);
+        let mut cells = HashMap::new();
+        for (i, brick) in self.bricks.iter().enumerate() {
+            for z in brick.low.z..=brick.high.z {
+                let row = height - z as usize;
+                for x in brick.low.x..=brick.high.x {
+                    cells.entry((x as usize, row)).or_insert(vec![]).push(i);
+                }
+                for y in brick.low.y..=brick.high.y {
+                    let column = width + 3 + y as usize;
+                    cells.entry((column, row)).or_insert(vec![]).push(i);
+                }
+            }
+        }
+        for ((column, row), bricks) in cells {
+            let (symbol, color) = match bricks[..] {
+                [i] => (char::from(b'A' + (i % 26) as u8), COLORS[i % COLORS.len()]),
+                _ => ('?', GRAY),
+            };
+            frame.set(column, row, symbol, color);
+        }
+        for column in (0..width).chain(width + 3..width + 3 + depth) {
+            frame.set(column, height, '-', GRAY);
+        }
+        frame
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+
+use rayon::prelude::*;
+
+use crate::{
+    parse::{self, grid},
+    types::*,
+};
+
+/// Junctions have to fit into the bitmask of visited junctions.
+const MAX_JUNCTIONS: usize = 128;
+/// The search branches this many junctions deep before the paths are split between threads.
+const SPLIT_DEPTH: usize = 6;
+
+pub struct Solver;
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    to: usize,
+    length: u32,
+    /// whether the slopes allow walking the edge in part 1
+    downhill: bool,
+}
+
+/// Junctions of the maze connected by the corridors between them.
+#[derive(Debug)]
+pub struct Trails {
+    edges: Vec<Vec<Edge>>,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> DaySolver<'a> for Solver {
+    type Input = Trails;
+
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let map = parse::parse_all(input, grid(|c| "#.^>v<".contains(c).then_some(c)))?;
+        let error = |line: usize, message: &str| ParseError {
+            line,
+            column: 1,
+            message: String::from(message),
+        };
+        let opening = |row: &[char]| {
+            let mut open = row.iter().enumerate().filter(|(_, &c)| c == '.');
+            match (open.next(), open.next()) {
+                (Some((x, _)), None) => Position::from_index(x, 0),
+                _ => None,
+            }
+        };
+        let start = opening(&map[0]).ok_or_else(|| error(1, "no single start in the top row"))?;
+        let end = opening(&map[map.len() - 1])
+            .map(|p| Position::new(p.x, map.len() as i64 - 1))
+            .ok_or_else(|| error(map.len(), "no single end in the bottom row"))?;
+        let trails = compress(&map, start, end);
+        let open = map.iter().flatten().filter(|&&c| c != '#').count();
+        let edges = trails.edges.iter().map(Vec::len).sum::<usize>();
+        test_print!(
+            test,
+            "compressed {open} open tiles into {} junctions and {edges} edges",
+            trails.edges.len()
+        );
+        Ok(trails)
+    }
+
+    fn solve1(&self, trails: &Self::Input, _test: bool) -> String {
+        trails.longest_hike(true)
+    }
+
+    fn solve2(&self, trails: &Self::Input, _test: bool) -> String {
+        trails.longest_hike(false)
+    }
+}
+
+fn slope(tile: char) -> Option<Direction> {
+    match tile {
+        '^' => Some(Direction::UP),
+        '>' => Some(Direction::RIGHT),
+        'v' => Some(Direction::DOWN),
+        '<' => Some(Direction::LEFT),
+        _ => None,
+    }
+}
+
+/// Build the graph of junctions, the start and the end, with the corridor lengths as edges.
+fn compress(map: &[Vec<char>], start: Position, end: Position) -> Trails {
+    let open = |pos: Position| pos.get(map).is_some_and(|&c| c != '#');
+    let neighbors = |pos: Position| Direction::ALL.into_iter().filter(move |&d| open(pos + d));
+    let mut junctions = HashMap::from([(start, 0)]);
+    // on a map with a single row the start is also the end, and the longest hike is empty
+    let end_index = *junctions.entry(end).or_insert(1);
+    for (y, row) in map.iter().enumerate() {
+        for x in 0..row.len() {
+            let pos = Position::from_index(x, y).unwrap();
+            if open(pos) && neighbors(pos).count() > 2 {
+                let index = junctions.len();
+                junctions.entry(pos).or_insert(index);
+            }
+        }
+    }
+    let mut edges = vec![vec![]; junctions.len()];
+    for (&junction, &from) in &junctions {
+        for first in neighbors(junction) {
+            // follow the corridor until the next junction, dead ends are dropped
+            let (mut pos, mut dir) = (junction, first);
+            let mut length = 0;
+            let mut downhill = true;
+            let to = loop {
+                downhill &= slope(*pos.get(map).unwrap()).is_none_or(|s| s == dir);
+                pos += dir;
+                length += 1;
+                if let Some(&to) = junctions.get(&pos) {
+                    break Some(to);
+                }
+                match neighbors(pos).find(|&d| d != -dir) {
+                    Some(next) => dir = next,
+                    None => break None,
+                }
+            };
+            if let Some(to) = to.filter(|&to| to != from) {
+                edges[from].push(Edge {
+                    to,
+                    length,
+                    downhill,
+                });
+            }
+        }
+    }
+    Trails {
+        edges,
+        start: 0,
+        end: end_index,
+    }
+}
+
+impl Trails {
+    fn longest_hike(&self, slopes: bool) -> String {
+        if self.edges.len() > MAX_JUNCTIONS {
+            return String::from("too many junctions");
+        }
+        let edges = self
+            .edges
+            .iter()
+            .map(|edges| {
+                let edges = edges.iter().filter(|e| e.downhill || !slopes);
+                edges.map(|e| (e.to, e.length)).collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // the last junction before the end has to go to the end, otherwise it's cut off
+        let before_end = edges.iter().enumerate().filter_map(|(from, edges)| {
+            let to_end = edges.iter().find(|&&(to, _)| to == self.end)?;
+            Some((from, *to_end))
+        });
+        let before_end = match before_end.collect::<Vec<_>>()[..] {
+            [single] => Some(single),
+            _ => None,
+        };
+        let search = Search {
+            edges: &edges,
+            end: self.end,
+            before_end,
+        };
+        // branch out sequentially, then search the remaining paths in parallel
+        let mut paths = vec![(self.start, 1u128 << self.start, 0)];
+        let mut longest = None;
+        for _ in 0..SPLIT_DEPTH {
+            let mut next = vec![];
+            for (at, visited, length) in paths {
+                if at == self.end {
+                    longest = longest.max(Some(length));
+                    continue;
+                }
+                next.extend(search.steps(at, visited, length));
+            }
+            paths = next;
+        }
+        let rest = paths
+            .into_par_iter()
+            .filter_map(|(at, visited, length)| search.longest(at, visited, length))
+            .max();
+        match longest.max(rest) {
+            Some(length) => length.to_string(),
+            None => String::from("unreachable"),
+        }
+    }
+}
+
+struct Search<'e> {
+    edges: &'e [Vec<(usize, u32)>],
+    end: usize,
+    before_end: Option<(usize, (usize, u32))>,
+}
+
+impl Search<'_> {
+    /// Junctions that can be walked to next, with the updated visited mask and length.
+    fn steps(
+        &self,
+        at: usize,
+        visited: u128,
+        length: u32,
+    ) -> impl Iterator<Item = (usize, u128, u32)> + '_ {
+        let edges = match self.before_end {
+            Some((before, ref to_end)) if before == at => std::slice::from_ref(to_end),
+            _ => &self.edges[at][..],
+        };
+        edges
+            .iter()
+            .filter(move |&&(to, _)| visited & 1 << to == 0)
+            .map(move |&(to, step)| (to, visited | 1 << to, length + step))
+    }
+
+    /// Depth first search for the longest path to the end, None if it can't be reached.
+    fn longest(&self, at: usize, visited: u128, length: u32) -> Option<u32> {
+        if at == self.end {
+            return Some(length);
+        }
+        self.steps(at, visited, length)
+            .filter_map(|(to, visited, length)| self.longest(to, visited, length))
+            .max()
+    }
+}
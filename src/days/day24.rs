@@ -0,0 +1,245 @@
+use std::{fmt, str::FromStr};
+
+use nom::{
+    character::complete::{char, space1},
+    combinator::map,
+    sequence::{pair, separated_pair, tuple},
+    IResult,
+};
+use num::{BigInt, BigRational, Signed, Zero};
+
+use crate::{
+    parse::{self, lines, number},
+    types::*,
+};
+
+/// Part 1 counts crossings in this area unless the solver is given another one.
+const AREA: Area = Area {
+    low: 200_000_000_000_000,
+    high: 400_000_000_000_000,
+};
+
+#[derive(Default)]
+pub struct Solver {
+    /// replaces [`AREA`] in part 1, the example uses `7,27`
+    pub area: Option<Area>,
+}
+
+/// Lowest and highest x and y of the test area of part 1, e.g. `7,27`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Area {
+    pub low: i64,
+    pub high: i64,
+}
+
+impl FromStr for Area {
+    type Err = ParseError;
+
+    fn from_str(area: &str) -> Result<Self, Self::Err> {
+        let bounds = separated_pair(number, char(','), number);
+        let (low, high) = parse::parse_all(area, bounds)?;
+        if low > high {
+            return Err(ParseError::new(
+                area,
+                area,
+                "the low end is above the high end",
+            ));
+        }
+        Ok(Self { low, high })
+    }
+}
+
+impl fmt::Display for Area {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.low, self.high)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Hailstone {
+    position: Position3,
+    velocity: Direction3,
+}
+
+impl<'a> DaySolver<'a> for Solver {
+    type Input = Vec<Hailstone>;
+
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let hailstones = parse::parse_all(input, lines(hailstone))?;
+        test_print!(test, "{} hailstones", hailstones.len());
+        Ok(hailstones)
+    }
+
+    fn solve1(&self, hailstones: &Self::Input, _test: bool) -> String {
+        let area = self.area.unwrap_or(AREA);
+        let mut count = 0;
+        for (i, a) in hailstones.iter().enumerate() {
+            for b in &hailstones[i + 1..] {
+                count += usize::from(crossing_in(a, b, area));
+            }
+        }
+        count.to_string()
+    }
+
+    fn solve2(&self, hailstones: &Self::Input, test: bool) -> String {
+        let Some(rock) = throw(hailstones) else {
+            return String::from("ambiguous");
+        };
+        let [position, velocity] = &rock;
+        test_print!(
+            test,
+            "rock thrown from {position:?} with velocity {velocity:?}"
+        );
+        if !hits_all(&rock, hailstones) {
+            return String::from("no throw");
+        }
+        position.iter().sum::<BigInt>().to_string()
+    }
+}
+
+fn triple(input: &str) -> IResult<&str, (i64, i64, i64)> {
+    let separator = || pair(char(','), space1);
+    map(
+        tuple((number, separator(), number, separator(), number)),
+        |(x, _, y, _, z)| (x, y, z),
+    )(input)
+}
+
+/// e.g. `19, 13, 30 @ -2,  1, -2`
+fn hailstone(input: &str) -> IResult<&str, Hailstone> {
+    map(
+        separated_pair(triple, tuple((space1, char('@'), space1)), triple),
+        |((x, y, z), (dx, dy, dz))| Hailstone {
+            position: Position3::new(x, y, z),
+            velocity: Direction3::new(dx, dy, dz),
+        },
+    )(input)
+}
+
+/// Whether the future xy paths of the hailstones cross inside the area. Parallel paths never
+/// cross, the real input has no hailstones moving along the same line.
+fn crossing_in(a: &Hailstone, b: &Hailstone, Area { low, high }: Area) -> bool {
+    let int = BigInt::from;
+    let (p, v, w) = (a.position, a.velocity, b.velocity);
+    let d = b.position - a.position;
+    let cross = |u: Direction3, d: Direction3| int(u.x) * int(d.y) - int(u.y) * int(d.x);
+    // solve p + t * v = q + s * w with Cramer's rule, t = t_det / det and s = s_det / det
+    let mut det = cross(w, v);
+    let (mut t_det, mut s_det) = (cross(w, d), cross(v, d));
+    if det.is_zero() {
+        return false;
+    }
+    if det.is_negative() {
+        (det, t_det, s_det) = (-det, -t_det, -s_det);
+    }
+    if t_det.is_negative() || s_det.is_negative() {
+        return false;
+    }
+    // low <= p + t * v <= high, multiplied by the positive determinant
+    let inside = |p: i64, v: i64| {
+        let offset = &t_det * int(v);
+        (int(low) - int(p)) * &det <= offset && offset <= (int(high) - int(p)) * &det
+    };
+    inside(p.x, v.x) && inside(p.y, v.y)
+}
+
+type Vector = [BigInt; 3];
+
+fn cross(a: &Vector, b: &Vector) -> Vector {
+    [
+        &a[1] * &b[2] - &a[2] * &b[1],
+        &a[2] * &b[0] - &a[0] * &b[2],
+        &a[0] * &b[1] - &a[1] * &b[0],
+    ]
+}
+
+fn minus(a: &Vector, b: &Vector) -> Vector {
+    [0, 1, 2].map(|i| &a[i] - &b[i])
+}
+
+impl Hailstone {
+    fn position(&self) -> Vector {
+        let p = self.position;
+        [p.x, p.y, p.z].map(BigInt::from)
+    }
+
+    fn velocity(&self) -> Vector {
+        let v = self.velocity;
+        [v.x, v.y, v.z].map(BigInt::from)
+    }
+}
+
+/// Position and velocity of the rock, None if the hailstones don't pin down a single throw.
+///
+/// The rock hits hailstone i iff `(P - p_i) x (V - v_i) = 0`. The product `P x V` is the same
+/// for all hailstones, so subtracting the equations of two hailstones leaves the linear system
+/// `P x (v_j - v_i) + (p_j - p_i) x V = p_j x v_j - p_i x v_i`, solved exactly with rationals.
+fn throw(hailstones: &[Hailstone]) -> Option<[Vector; 2]> {
+    let (first, rest) = hailstones.split_first()?;
+    let unit = |i: usize| [0, 1, 2].map(|j| BigInt::from(u8::from(i == j)));
+    let (p, v) = (first.position(), first.velocity());
+    // Gauss-Jordan elimination one row at a time, until every unknown has a pivot
+    let mut pivots: Vec<(usize, Vec<BigRational>)> = vec![];
+    for other in rest {
+        let (q, w) = (other.position(), other.velocity());
+        let (dp, dv) = (minus(&q, &p), minus(&w, &v));
+        let constant = minus(&cross(&q, &w), &cross(&p, &v));
+        // the coefficients of every unknown are the cross products with the unit vectors
+        let at_p = [0, 1, 2].map(|i| cross(&unit(i), &dv));
+        let at_v = [0, 1, 2].map(|i| cross(&dp, &unit(i)));
+        for (k, constant) in constant.into_iter().enumerate() {
+            let coefficients = at_p.iter().chain(&at_v).map(|c| c[k].clone());
+            let mut row = coefficients
+                .chain([constant])
+                .map(BigRational::from_integer)
+                .collect::<Vec<_>>();
+            for (column, pivot) in &pivots {
+                eliminate(&mut row, pivot, *column);
+            }
+            let Some(column) = (0..6).find(|&c| !row[c].is_zero()) else {
+                continue;
+            };
+            for (_, pivot) in &mut pivots {
+                eliminate(pivot, &row, column);
+            }
+            pivots.push((column, row));
+        }
+        if pivots.len() == 6 {
+            break;
+        }
+    }
+    if pivots.len() < 6 {
+        return None;
+    }
+    pivots.sort_by_key(|&(column, _)| column);
+    // a throw from between the integer coordinates is checked against the hailstones later
+    let value = |i: usize| {
+        let (_, row) = &pivots[i];
+        (&row[6] / &row[i]).round().to_integer()
+    };
+    Some([[0, 1, 2].map(value), [3, 4, 5].map(value)])
+}
+
+/// Subtract a multiple of the pivot row to clear the column in the row.
+fn eliminate(row: &mut [BigRational], pivot: &[BigRational], column: usize) {
+    if !row[column].is_zero() {
+        let factor = &row[column] / &pivot[column];
+        for (value, p) in row.iter_mut().zip(pivot) {
+            *value -= &factor * p;
+        }
+    }
+}
+
+/// Whether the rock thrown from the position and with the velocity hits every hailstone at
+/// some point in the future.
+fn hits_all([position, velocity]: &[Vector; 2], hailstones: &[Hailstone]) -> bool {
+    hailstones.iter().all(|h| {
+        let d = minus(&h.position(), position);
+        let w = minus(velocity, &h.velocity());
+        // the rock reaches the hailstone at the time t with t * w = d
+        let parallel = cross(&d, &w).iter().all(Zero::is_zero);
+        let future = (0..3).all(|i| !(&d[i] * w[i].signum()).is_negative());
+        let moving = w.iter().any(|c| !c.is_zero()) || d.iter().all(Zero::is_zero);
+        parallel && future && moving
+    })
+}
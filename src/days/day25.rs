@@ -0,0 +1,178 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, space1},
+    multi::separated_list1,
+    sequence::separated_pair,
+    IResult,
+};
+
+use crate::{
+    parse::{self, lines},
+    types::*,
+};
+
+/// Number of wires that have to be cut.
+const CUT: usize = 3;
+
+pub struct Solver;
+
+/// Components connected by wires, each wire is stored once with its ends in input order.
+#[derive(Debug)]
+pub struct Wiring<'a> {
+    names: Vec<&'a str>,
+    wires: Vec<(usize, usize)>,
+    /// neighbor and wire for every component
+    adjacent: Vec<Vec<(usize, usize)>>,
+}
+
+impl<'a> DaySolver<'a> for Solver {
+    type Input = Wiring<'a>;
+
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError> {
+        let connections = parse::parse_all(input, lines(connection))?;
+        let wiring = Wiring::new(connections);
+        test_print!(
+            test,
+            "{} components and {} wires",
+            wiring.names.len(),
+            wiring.wires.len()
+        );
+        Ok(wiring)
+    }
+
+    fn solve1(&self, wiring: &Self::Input, test: bool) -> String {
+        let Some(side) = wiring.three_wire_cut() else {
+            return String::from("no three wire cut");
+        };
+        let cut = wiring.wires.iter().filter(|&&(a, b)| side[a] != side[b]);
+        let cut = cut.map(|&(a, b)| format!("{}/{}", wiring.names[a], wiring.names[b]));
+        test_print!(test, "cut {}", cut.collect::<Vec<_>>().join(", "));
+        let group = side.iter().filter(|&&s| s).count();
+        (group * (side.len() - group)).to_string()
+    }
+
+    fn solve2(&self, _wiring: &Self::Input, _test: bool) -> String {
+        // the last day only has one puzzle
+        String::from("Merry Christmas")
+    }
+}
+
+/// e.g. `jqt: rhn xhk nvd`
+fn connection(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
+    separated_pair(alpha1, tag(": "), separated_list1(space1, alpha1))(input)
+}
+
+impl<'a> Wiring<'a> {
+    fn new(connections: Vec<(&'a str, Vec<&'a str>)>) -> Self {
+        let mut indices = HashMap::new();
+        let mut names = vec![];
+        let mut index = |name: &'a str| {
+            *indices.entry(name).or_insert_with(|| {
+                names.push(name);
+                names.len() - 1
+            })
+        };
+        // wires listed from both ends are only connected once
+        let mut seen = HashSet::new();
+        let mut wires = vec![];
+        for (from, others) in connections {
+            let a = index(from);
+            for other in others {
+                let b = index(other);
+                if a != b && seen.insert((a.min(b), a.max(b))) {
+                    wires.push((a, b));
+                }
+            }
+        }
+        let mut adjacent = vec![vec![]; names.len()];
+        for (wire, &(a, b)) in wires.iter().enumerate() {
+            adjacent[a].push((b, wire));
+            adjacent[b].push((a, wire));
+        }
+        Self {
+            names,
+            wires,
+            adjacent,
+        }
+    }
+
+    /// Components on the side of the first one after cutting three wires into two groups, None
+    /// if fewer wires already split the components or more are needed.
+    ///
+    /// Every cut separates the first component from some other one, so the cut is found as a
+    /// flow of three from the first component to the first one that it can't send four to.
+    fn three_wire_cut(&self) -> Option<Vec<bool>> {
+        for sink in 1..self.names.len() {
+            match self.max_flow(0, sink, CUT) {
+                None => continue,
+                Some((CUT, side)) => {
+                    // the source side is connected by the search, the sink side might not be
+                    let first = side.iter().position(|&s| !s).unwrap();
+                    let group = self.group(first, |c| !side[c]);
+                    return (group == side.iter().filter(|&&s| !s).count()).then_some(side);
+                }
+                Some(_) => return None,
+            }
+        }
+        None
+    }
+
+    /// Number of components connected to the start without leaving the allowed ones.
+    fn group(&self, start: usize, allowed: impl Fn(usize) -> bool) -> usize {
+        let mut seen = vec![false; self.names.len()];
+        seen[start] = true;
+        let mut stack = vec![start];
+        let mut count = 0;
+        while let Some(at) = stack.pop() {
+            count += 1;
+            for &(next, _) in &self.adjacent[at] {
+                if allowed(next) && !seen[next] {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        count
+    }
+
+    /// Maximum flow with unit capacities from the source to the sink and the components still
+    /// reachable from the source afterwards, None if the flow is larger than the limit.
+    fn max_flow(&self, source: usize, sink: usize, limit: usize) -> Option<(usize, Vec<bool>)> {
+        // the flow along every wire from its first end to its second
+        let mut flow = vec![0i8; self.wires.len()];
+        let residual = |flow: &[i8], from: usize, wire: usize| {
+            if self.wires[wire].0 == from {
+                1 - flow[wire]
+            } else {
+                1 + flow[wire]
+            }
+        };
+        for paths in 0..=limit {
+            // breadth first search for an augmenting path
+            let mut parent = vec![None; self.names.len()];
+            let mut reached = vec![false; self.names.len()];
+            reached[source] = true;
+            let mut queue = VecDeque::from([source]);
+            while let Some(at) = queue.pop_front() {
+                for &(next, wire) in &self.adjacent[at] {
+                    if !reached[next] && residual(&flow, at, wire) > 0 {
+                        reached[next] = true;
+                        parent[next] = Some((at, wire));
+                        queue.push_back(next);
+                    }
+                }
+            }
+            if !reached[sink] {
+                return Some((paths, reached));
+            }
+            let mut at = sink;
+            while let Some((from, wire)) = parent[at] {
+                flow[wire] += if self.wires[wire].0 == from { 1 } else { -1 };
+                at = from;
+            }
+        }
+        None
+    }
+}
@@ -1,4 +1,11 @@
-use crate::types::DaySolver;
+use std::io::{self, BufRead, Write};
+
+use crate::{
+    explain::Explanation,
+    parse::ParseError,
+    types::{DaySolver, Task, Timings},
+    RunOptions,
+};
 
 mod day01;
 mod day02;
@@ -19,36 +26,214 @@ mod day16;
 mod day17;
 mod day18;
 mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+pub const DAY_COUNT: usize = 25;
+
+/// Source of every solver, part of the cache fingerprint so changed solvers are solved again.
+const SOURCES: [&str; DAY_COUNT] = [
+    include_str!("day01.rs"),
+    include_str!("day02.rs"),
+    include_str!("day03.rs"),
+    include_str!("day04.rs"),
+    include_str!("day05.rs"),
+    include_str!("day06.rs"),
+    include_str!("day07.rs"),
+    include_str!("day08.rs"),
+    include_str!("day09.rs"),
+    include_str!("day10.rs"),
+    include_str!("day11.rs"),
+    include_str!("day12.rs"),
+    include_str!("day13.rs"),
+    include_str!("day14.rs"),
+    include_str!("day15.rs"),
+    include_str!("day16.rs"),
+    include_str!("day17.rs"),
+    include_str!("day18.rs"),
+    include_str!("day19.rs"),
+    include_str!("day20.rs"),
+    include_str!("day21.rs"),
+    include_str!("day22.rs"),
+    include_str!("day23.rs"),
+    include_str!("day24.rs"),
+    include_str!("day25.rs"),
+];
+
+pub use day01::{calibration_with, digit_words, Warning};
+pub use day02::{Bag, ColorStats, Game};
+pub use day24::Area;
+
+pub(crate) fn source(day: usize) -> &'static str {
+    SOURCES[day - 1]
+}
 
-pub const DAY_COUNT: usize = 19;
+/// Run `$body` with `$solver` bound to the solver of the given day.
+macro_rules! with_solver {
+    ($day:expr, $solver:ident => $body:expr) => {
+        match $day {
+            1 => {
+                let $solver = day01::Solver;
+                $body
+            }
+            2 => {
+                let $solver = day02::Solver::default();
+                $body
+            }
+            3 => {
+                let $solver = day03::Solver;
+                $body
+            }
+            4 => {
+                let $solver = day04::Solver;
+                $body
+            }
+            5 => {
+                let $solver = day05::Solver;
+                $body
+            }
+            6 => {
+                let $solver = day06::Solver;
+                $body
+            }
+            7 => {
+                let $solver = day07::Solver;
+                $body
+            }
+            8 => {
+                let $solver = day08::Solver;
+                $body
+            }
+            9 => {
+                let $solver = day09::Solver;
+                $body
+            }
+            10 => {
+                let $solver = day10::Solver;
+                $body
+            }
+            11 => {
+                let $solver = day11::Solver;
+                $body
+            }
+            12 => {
+                let $solver = day12::Solver;
+                $body
+            }
+            13 => {
+                let $solver = day13::Solver;
+                $body
+            }
+            14 => {
+                let $solver = day14::Solver;
+                $body
+            }
+            15 => {
+                let $solver = day15::Solver;
+                $body
+            }
+            16 => {
+                let $solver = day16::Solver;
+                $body
+            }
+            17 => {
+                let $solver = day17::Solver;
+                $body
+            }
+            18 => {
+                let $solver = day18::Solver;
+                $body
+            }
+            19 => {
+                let $solver = day19::Solver;
+                $body
+            }
+            20 => {
+                let $solver = day20::Solver;
+                $body
+            }
+            21 => {
+                let $solver = day21::Solver;
+                $body
+            }
+            22 => {
+                let $solver = day22::Solver;
+                $body
+            }
+            23 => {
+                let $solver = day23::Solver;
+                $body
+            }
+            24 => {
+                let $solver = day24::Solver::default();
+                $body
+            }
+            25 => {
+                let $solver = day25::Solver;
+                $body
+            }
+            _ => panic!("invalid day"),
+        }
+    };
+}
 
+/// The bag only changes part 1 of day 2 and the area part 1 of day 24, the other days ignore
+/// them.
 pub(crate) fn solve(
     day: usize,
     input: &str,
-    test: bool,
-    task: crate::types::Task,
-    print_times: bool,
-) -> (String, String) {
+    options: &RunOptions,
+) -> Result<(String, String, Timings), ParseError> {
+    let RunOptions {
+        test,
+        task,
+        print_times,
+        bag,
+        area,
+        ..
+    } = *options;
     match day {
-        1 => day01::Solver.solve(day, input, test, task, print_times),
-        3 => day03::Solver.solve(day, input, test, task, print_times),
-        4 => day04::Solver.solve(day, input, test, task, print_times),
-        2 => day02::Solver.solve(day, input, test, task, print_times),
-        5 => day05::Solver.solve(day, input, test, task, print_times),
-        6 => day06::Solver.solve(day, input, test, task, print_times),
-        7 => day07::Solver.solve(day, input, test, task, print_times),
-        8 => day08::Solver.solve(day, input, test, task, print_times),
-        9 => day09::Solver.solve(day, input, test, task, print_times),
-        10 => day10::Solver.solve(day, input, test, task, print_times),
-        11 => day11::Solver.solve(day, input, test, task, print_times),
-        12 => day12::Solver.solve(day, input, test, task, print_times),
-        13 => day13::Solver.solve(day, input, test, task, print_times),
-        14 => day14::Solver.solve(day, input, test, task, print_times),
-        15 => day15::Solver.solve(day, input, test, task, print_times),
-        16 => day16::Solver.solve(day, input, test, task, print_times),
-        17 => day17::Solver.solve(day, input, test, task, print_times),
-        18 => day18::Solver.solve(day, input, test, task, print_times),
-        19 => day19::Solver.solve(day, input, test, task, print_times),
-        _ => panic!("invalid day"),
+        2 => run(day02::Solver { bag }, day, input, test, task, print_times),
+        24 => run(day24::Solver { area }, day, input, test, task, print_times),
+        _ => with_solver!(day, solver => run(solver, day, input, test, task, print_times)),
     }
 }
+
+pub(crate) fn explain(day: usize, input: &str) -> Result<Option<Explanation>, ParseError> {
+    with_solver!(day, solver => explain_with(solver, input))
+}
+
+pub(crate) fn explore(
+    day: usize,
+    input: String,
+    test: bool,
+    commands: &mut impl BufRead,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    with_solver!(day, solver => crate::explore::run(solver, input, test, commands, out))
+}
+
+fn run<S: for<'a> DaySolver<'a>>(
+    solver: S,
+    day: usize,
+    input: &str,
+    test: bool,
+    task: Task,
+    print_times: bool,
+) -> Result<(String, String, Timings), ParseError> {
+    let input = <S as DaySolver>::NORMALIZE.apply(input);
+    solver.solve(day, &input, test, task, print_times)
+}
+
+fn explain_with<S: for<'a> DaySolver<'a>>(
+    solver: S,
+    input: &str,
+) -> Result<Option<Explanation>, ParseError> {
+    let input = <S as DaySolver>::NORMALIZE.apply(input);
+    let parsed = <S as DaySolver>::parse_input(&input, false)?;
+    Ok(solver.explain(&parsed))
+}
@@ -0,0 +1,56 @@
+//! Human readable traces of how a solver reached its answers, shown with `--explain`.
+
+use std::fmt::{self, Display};
+
+/// Tables describing the steps of a solution, e.g. one row per card.
+#[derive(Debug, Default)]
+pub struct Explanation {
+    pub sections: Vec<Section>,
+}
+
+#[derive(Debug)]
+pub struct Section {
+    pub title: String,
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Explanation {
+    /// Add a table, every row needs a value for each column.
+    pub fn section(mut self, title: &str, columns: &[&str], rows: Vec<Vec<String>>) -> Self {
+        assert!(rows.iter().all(|row| row.len() == columns.len()));
+        self.sections.push(Section {
+            title: String::from(title),
+            columns: columns.iter().map(|&c| String::from(c)).collect(),
+            rows,
+        });
+        self
+    }
+}
+
+impl Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for section in &self.sections {
+            writeln!(f, "### {}", section.title)?;
+            let widths = (0..section.columns.len())
+                .map(|i| {
+                    let cells = section.rows.iter().map(|row| row[i].len());
+                    cells.chain([section.columns[i].len(), 3]).max().unwrap()
+                })
+                .collect::<Vec<_>>();
+            let line = |f: &mut fmt::Formatter<'_>, cells: &[String]| {
+                let cells = cells.iter().zip(&widths);
+                let cells = cells.map(|(cell, width)| format!("{cell:<width$}"));
+                writeln!(f, "{}", cells.collect::<Vec<_>>().join(" | ").trim_end())
+            };
+            line(f, &section.columns)?;
+            let separator = widths.iter().map(|&w| "-".repeat(w)).collect::<Vec<_>>();
+            line(f, &separator)?;
+            for row in &section.rows {
+                line(f, row)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use crate::{parse::ParseError, types::DaySolver, util::panic_message};
+
+const HELP: &str = concat!(
+    "  1, solve1                    run part 1\n",
+    "  2, solve2                    run part 2\n",
+    "  load <file>                  parse another input file\n",
+    "  debug                        toggle debug output\n",
+    "  times                        show parsing and solving times\n",
+    "  help                         show this help\n",
+    "  quit                         leave explore mode",
+);
+
+/// Parse the input once and run commands read from `commands` against it.
+pub(crate) fn run<S: for<'a> DaySolver<'a>>(
+    solver: S,
+    mut input: String,
+    mut test: bool,
+    commands: &mut impl BufRead,
+    out: &mut impl Write,
+) -> io::Result<()> {
+    loop {
+        // parsed input borrows the raw input, so it must be dropped before loading the next one
+        input = {
+            let normalized = <S as DaySolver>::NORMALIZE.apply(&input);
+            let start = Instant::now();
+            let parsed = match <S as DaySolver>::parse_input(&normalized, test) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    writeln!(out, "could not parse input: {e}")?;
+                    return Ok(());
+                }
+            };
+            let parsing = start.elapsed();
+            writeln!(out, "parsed input in {parsing:.2?}")?;
+            let mut times: [Option<Duration>; 2] = [None, None];
+            loop {
+                write!(out, "> ")?;
+                out.flush()?;
+                let mut line = String::new();
+                if commands.read_line(&mut line)? == 0 {
+                    return Ok(());
+                }
+                let mut words = line.split_whitespace();
+                let Some(command) = words.next() else {
+                    continue;
+                };
+                let args = words.collect::<Vec<_>>();
+                match command {
+                    "1" | "solve1" | "2" | "solve2" => {
+                        let part = if command.ends_with('1') { 1 } else { 2 };
+                        let start = Instant::now();
+                        let res = guarded(|| {
+                            if part == 1 {
+                                solver.solve1(&parsed, test)
+                            } else {
+                                solver.solve2(&parsed, test)
+                            }
+                        });
+                        let time = start.elapsed();
+                        match res {
+                            Ok(res) => {
+                                times[part - 1] = Some(time);
+                                writeln!(out, "part {part}: {res} ({time:.2?})")?;
+                            }
+                            Err(e) => writeln!(out, "part {part} panicked: {e}")?,
+                        }
+                    }
+                    "load" => match args.first().map(|path| (path, fs::read_to_string(path))) {
+                        // keep the current input if the new one is malformed
+                        Some((_, Ok(new_input))) => match check::<S>(&new_input) {
+                            Ok(()) => break new_input,
+                            Err(e) => writeln!(out, "could not parse input: {e}")?,
+                        },
+                        Some((path, Err(e))) => writeln!(out, "could not read '{path}': {e}")?,
+                        None => writeln!(out, "usage: load <file>")?,
+                    },
+                    "debug" => {
+                        test = !test;
+                        writeln!(out, "debug output {}", if test { "on" } else { "off" })?;
+                    }
+                    "times" => {
+                        writeln!(out, "parsing: {parsing:>8.2?}")?;
+                        for (i, time) in times.iter().enumerate() {
+                            match time {
+                                Some(time) => writeln!(out, "task {}: {time:>8.2?}", i + 1)?,
+                                None => writeln!(out, "task {}: not run", i + 1)?,
+                            }
+                        }
+                    }
+                    "help" => {
+                        writeln!(out, "{HELP}")?;
+                        if !<S as DaySolver>::EXPLORE_HELP.is_empty() {
+                            writeln!(out, "{}", <S as DaySolver>::EXPLORE_HELP)?;
+                        }
+                    }
+                    "quit" | "exit" => return Ok(()),
+                    _ => match guarded(|| solver.explore(&parsed, command, &args)) {
+                        Ok(Some(res)) => writeln!(out, "{res}")?,
+                        Ok(None) => writeln!(out, "unknown command '{command}', try 'help'")?,
+                        Err(e) => writeln!(out, "'{command}' panicked: {e}")?,
+                    },
+                }
+            }
+        };
+    }
+}
+
+fn check<S: for<'a> DaySolver<'a>>(input: &str) -> Result<(), ParseError> {
+    let normalized = <S as DaySolver>::NORMALIZE.apply(input);
+    <S as DaySolver>::parse_input(&normalized, false).map(|_| ())
+}
+
+/// Run a solver call, a panic is reported with its message instead of ending the session.
+fn guarded<T>(call: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(call)).map_err(|e| panic_message(&*e).to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Panicking;
+
+    impl<'a> DaySolver<'a> for Panicking {
+        type Input = ();
+
+        fn parse_input(_input: &'a str, _test: bool) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn solve1(&self, _input: &Self::Input, _test: bool) -> String {
+            panic!("attempt to multiply with overflow")
+        }
+
+        fn solve2(&self, _input: &Self::Input, _test: bool) -> String {
+            String::from("42")
+        }
+
+        fn explore(&self, _input: &Self::Input, _command: &str, _args: &[&str]) -> Option<String> {
+            panic!("index out of bounds")
+        }
+    }
+
+    #[test]
+    fn solver_panics() {
+        let mut out = vec![];
+        let commands = "1\nfoo\n2\ntimes\n";
+        run(
+            Panicking,
+            String::new(),
+            false,
+            &mut commands.as_bytes(),
+            &mut out,
+        )
+        .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.contains("part 1 panicked: attempt to multiply with overflow"),
+            "{out}"
+        );
+        assert!(out.contains("'foo' panicked: index out of bounds"), "{out}");
+        assert!(out.contains("part 2: 42"), "{out}");
+        assert!(out.contains("task 1: not run"), "{out}");
+    }
+}
@@ -0,0 +1,153 @@
+//! C ABI of the solvers, built into the cdylib. The header `ffi/aoc23.h` is generated by
+//! [`header`] and the tests check that it is up to date.
+
+use std::{ffi::c_char, panic, slice};
+
+use crate::{try_solve, Task, DAY_COUNT};
+
+/// Returned by `aoc23_solve`.
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Ok = 0,
+    InvalidDay = 1,
+    InvalidTask = 2,
+    InvalidUtf8 = 3,
+    ParseError = 4,
+    BufferTooSmall = 5,
+    Panic = 6,
+}
+
+/// Name in the header and description of every status.
+const STATUSES: [(Status, &str, &str); 7] = [
+    (Status::Ok, "OK", "both answers were written"),
+    (
+        Status::InvalidDay,
+        "INVALID_DAY",
+        "there is no solver for the day",
+    ),
+    (
+        Status::InvalidTask,
+        "INVALID_TASK",
+        "the task is not one of AOC23_TASK_*",
+    ),
+    (
+        Status::InvalidUtf8,
+        "INVALID_UTF8",
+        "the input is not valid UTF-8",
+    ),
+    (
+        Status::ParseError,
+        "PARSE_ERROR",
+        "the input is malformed, out1 holds the error message",
+    ),
+    (
+        Status::BufferTooSmall,
+        "BUFFER_TOO_SMALL",
+        "an answer and its terminating NUL don't fit into out_len bytes",
+    ),
+    (Status::Panic, "PANIC", "the solver panicked, this is a bug"),
+];
+
+const TASKS: [(&str, u32); 3] = [("BOTH", 0), ("ONE", 1), ("TWO", 2)];
+
+/// Solve a day and write both answers as NUL terminated strings, an answer of a part that
+/// wasn't solved is empty.
+///
+/// # Safety
+/// `input` has to point to `input_len` readable bytes, or may be null if `input_len` is 0.
+/// `out1` and `out2` have to point to `out_len` writable bytes each.
+#[no_mangle]
+pub unsafe extern "C" fn aoc23_solve(
+    day: u32,
+    task: u32,
+    input: *const u8,
+    input_len: usize,
+    out1: *mut c_char,
+    out2: *mut c_char,
+    out_len: usize,
+) -> i32 {
+    let day = day as usize;
+    if !(1..=DAY_COUNT).contains(&day) {
+        return Status::InvalidDay as i32;
+    }
+    let task = match task {
+        0 => Task::Both,
+        1 => Task::One,
+        2 => Task::Two,
+        _ => return Status::InvalidTask as i32,
+    };
+    let input = if input_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    let Ok(input) = std::str::from_utf8(input) else {
+        return Status::InvalidUtf8 as i32;
+    };
+    // unwinding into C is undefined behavior
+    let status = match panic::catch_unwind(|| try_solve(day, input, task)) {
+        Ok(Ok((res1, res2, _))) => {
+            if !write(&res1, out1, out_len) || !write(&res2, out2, out_len) {
+                return Status::BufferTooSmall as i32;
+            }
+            Status::Ok
+        }
+        Ok(Err(e)) => {
+            // the message is cut off if it doesn't fit
+            let message = e.to_string();
+            let fits = message.len().min(out_len.saturating_sub(1));
+            let end = (0..=fits).rev().find(|&i| message.is_char_boundary(i));
+            write(&message[..end.unwrap_or(0)], out1, out_len);
+            Status::ParseError
+        }
+        Err(_) => Status::Panic,
+    };
+    status as i32
+}
+
+/// Copy `s` and a terminating NUL to `out`, returns false if they don't fit.
+unsafe fn write(s: &str, out: *mut c_char, out_len: usize) -> bool {
+    if out.is_null() || s.len() >= out_len {
+        return false;
+    }
+    let out = slice::from_raw_parts_mut(out.cast::<u8>(), s.len() + 1);
+    out[..s.len()].copy_from_slice(s.as_bytes());
+    out[s.len()] = 0;
+    true
+}
+
+/// The C header for the cdylib.
+pub fn header() -> String {
+    let mut header = String::from(concat!(
+        "/* Generated by aoc23::ffi::header, don't edit it by hand. */\n",
+        "#ifndef AOC23_H\n",
+        "#define AOC23_H\n",
+        "\n",
+        "#include <stddef.h>\n",
+        "#include <stdint.h>\n",
+        "\n",
+    ));
+    header += &format!("#define AOC23_DAY_COUNT {DAY_COUNT}\n\n");
+    for (name, value) in TASKS {
+        header += &format!("#define AOC23_TASK_{name} {value}\n");
+    }
+    header += "\n/* status codes returned by aoc23_solve */\n";
+    for (status, name, description) in STATUSES {
+        header += &format!(
+            "#define AOC23_{name} {} /* {description} */\n",
+            status as i32
+        );
+    }
+    header += concat!(
+        "\n",
+        "/* Solve a day and write both answers as NUL terminated strings to out1 and out2,\n",
+        " * which both have room for out_len bytes. input needs to be valid UTF-8 and doesn't\n",
+        " * need to be NUL terminated. The answer of a part that wasn't solved is empty. */\n",
+        "int32_t aoc23_solve(uint32_t day, uint32_t task, const uint8_t *input, size_t input_len,\n",
+        "                    char *out1, char *out2, size_t out_len);\n",
+        "\n",
+        "#endif\n",
+    );
+    header
+}
@@ -0,0 +1,668 @@
+//! Synthetic inputs of a chosen size, used to see how the solvers scale. The same day, size
+//! and seed always give the same input.
+//!
+//! day | size
+//! --: | :---
+//!  3, 10, 11, 14, 16, 17, 21 | width and height of the grid
+//!  1, 2, 4, 7, 9, 12 | number of lines
+//!  5 | ranges per map
+//!  6 | ignored, part 2 concatenates the numbers so they stay as small as in the real input
+//!  8 | roughly the number of nodes, at least 300
+//! 13 | number of patterns
+//! 15 | number of steps
+//! 18 | number of columns of the dug out shape
+//! 19 | number of parts, a quarter as many workflows
+//! 20 | number of 12 bit counters feeding `rx`, part 2 overflows with more than 5
+//! 22 | number of bricks
+//! 23 | width and height of the map, junctions are a lattice of at most 6x6 like in the real input
+//! 24 | number of hailstones, all of them are hit by the same rock
+//! 25 | number of components, in two groups connected by three wires
+
+use crate::DAY_COUNT;
+
+/// Generate a valid input for the given day.
+pub fn generate(day: usize, size: usize, seed: u64) -> String {
+    assert!((1..=DAY_COUNT).contains(&day), "invalid day {day}");
+    let size = size.max(1);
+    let rng = &mut Rng(seed);
+    match day {
+        1 => day01(rng, size),
+        2 => day02(rng, size),
+        3 => day03(rng, size),
+        4 => day04(rng, size),
+        5 => day05(rng, size),
+        6 => day06(rng),
+        7 => day07(rng, size),
+        8 => day08(rng, size),
+        9 => day09(rng, size),
+        10 => day10(rng, size),
+        11 => grid(rng, size, |rng| if rng.chance(2) { '#' } else { '.' }),
+        12 => day12(rng, size),
+        13 => day13(rng, size),
+        14 => grid(rng, size, |rng| match rng.below(10) {
+            0..=1 => 'O',
+            2 => '#',
+            _ => '.',
+        }),
+        15 => day15(rng, size),
+        16 => grid(rng, size, |rng| {
+            if rng.chance(10) {
+                rng.pick(&['/', '\\', '|', '-'])
+            } else {
+                '.'
+            }
+        }),
+        17 => grid(rng, size, |rng| {
+            rng.pick(&['1', '2', '3', '4', '5', '6', '7', '8', '9'])
+        }),
+        18 => day18(rng, size),
+        19 => day19(rng, size),
+        20 => day20(rng, size),
+        21 => day21(rng, size),
+        22 => day22(rng, size),
+        23 => day23(rng, size),
+        24 => day24(rng, size),
+        25 => day25(rng, size),
+        _ => unreachable!(),
+    }
+}
+
+/// SplitMix64, reproducible without pulling in a random number crate.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// Number in `low..=high`.
+    fn between(&mut self, low: usize, high: usize) -> usize {
+        low + self.below(high - low + 1)
+    }
+
+    fn chance(&mut self, percent: usize) -> bool {
+        self.below(100) < percent
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+fn lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+fn grid(rng: &mut Rng, size: usize, mut cell: impl FnMut(&mut Rng) -> char) -> String {
+    lines((0..size).map(|_| (0..size).map(|_| cell(rng)).collect()))
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn day01(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let mut line = String::new();
+        let len = rng.between(8, 30);
+        while line.len() < len {
+            match rng.below(10) {
+                0 => line.push(char::from(b'1' + rng.below(9) as u8)),
+                1..=2 => line += rng.pick(&DIGIT_WORDS),
+                _ => line.push(char::from(b'a' + rng.below(26) as u8)),
+            }
+        }
+        // part 1 needs a digit on every line
+        let at = rng.below(line.len() + 1);
+        line.insert(at, char::from(b'1' + rng.below(9) as u8));
+        line
+    }))
+}
+
+fn day02(rng: &mut Rng, size: usize) -> String {
+    lines((1..=size).map(|id| {
+        let draws = (0..rng.between(1, 6)).map(|_| {
+            let mut colors = ["red", "green", "blue"];
+            rng.shuffle(&mut colors);
+            let cubes = colors[..rng.between(1, 3)]
+                .iter()
+                .map(|color| format!("{} {color}", rng.between(1, 20)));
+            cubes.collect::<Vec<_>>().join(", ")
+        });
+        format!("Game {id}: {}", draws.collect::<Vec<_>>().join("; "))
+    }))
+}
+
+fn day03(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        let mut row = String::new();
+        while row.len() < size {
+            match rng.below(10) {
+                0..=1 => row += &format!("{}.", rng.between(1, 999)),
+                2 => row.push(rng.pick(&['*', '#', '+', '$', '/', '=', '@', '%', '&', '-'])),
+                _ => row.push('.'),
+            }
+        }
+        row.truncate(size);
+        row
+    }))
+}
+
+/// Distinct numbers in `1..=99`, right aligned like in the real input.
+fn card_numbers(rng: &mut Rng, count: usize) -> String {
+    let mut numbers = (1..=99).collect::<Vec<_>>();
+    rng.shuffle(&mut numbers);
+    let numbers = numbers[..count].iter().map(|n| format!("{n:>2}"));
+    numbers.collect::<Vec<_>>().join(" ")
+}
+
+fn day04(rng: &mut Rng, size: usize) -> String {
+    // few matches, otherwise the number of copies grows exponentially
+    lines((1..=size).map(|id| {
+        format!(
+            "Card {id:>4}: {} | {}",
+            card_numbers(rng, 5),
+            card_numbers(rng, 8)
+        )
+    }))
+}
+
+fn day05(rng: &mut Rng, size: usize) -> String {
+    const MAPS: [&str; 7] = [
+        "seed-to-soil",
+        "soil-to-fertilizer",
+        "fertilizer-to-water",
+        "water-to-light",
+        "light-to-temperature",
+        "temperature-to-humidity",
+        "humidity-to-location",
+    ];
+    let max = 1 << 32;
+    let seeds = (0..10).map(|i| {
+        let value = if i % 2 == 0 {
+            rng.below(max)
+        } else {
+            rng.below(1 << 28)
+        };
+        value.to_string()
+    });
+    let mut input = format!("seeds: {}\n", seeds.collect::<Vec<_>>().join(" "));
+    for name in MAPS {
+        input += &format!("\n{name} map:\n");
+        input += &lines((0..size).map(|_| {
+            let len = rng.between(1, max / size);
+            format!("{} {} {len}", rng.below(max), rng.below(max))
+        }));
+    }
+    input
+}
+
+fn day06(rng: &mut Rng) -> String {
+    let races = (0..4)
+        .map(|_| {
+            let time = rng.between(10, 99);
+            (time, rng.between(1, time * time / 4 - 1))
+        })
+        .collect::<Vec<_>>();
+    let column = |n: usize| format!("{n:>5}");
+    format!(
+        "Time:    {}\nDistance:{}\n",
+        races.iter().map(|r| column(r.0)).collect::<String>(),
+        races.iter().map(|r| column(r.1)).collect::<String>()
+    )
+}
+
+fn day07(rng: &mut Rng, size: usize) -> String {
+    const CARDS: [char; 13] = [
+        '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A',
+    ];
+    lines((0..size).map(|_| {
+        let hand = (0..5).map(|_| rng.pick(&CARDS)).collect::<String>();
+        format!("{hand} {}", rng.between(1, 1000))
+    }))
+}
+
+fn day08(rng: &mut Rng, size: usize) -> String {
+    // every start leads through its own chain to an end, which leads back to the start of
+    // the chain, so the number of moves repeats like in the real input
+    const PRIMES: [usize; 6] = [41, 43, 47, 53, 59, 61];
+    let scale = (size / 300).max(1);
+    let directions = (0..scale)
+        .map(|_| rng.pick(&['L', 'R']))
+        .collect::<String>();
+    let mut nodes = vec![];
+    for (ghost, prime) in PRIMES.iter().enumerate() {
+        let name = |i: usize| match i {
+            0 if ghost == 0 => String::from("AAA"),
+            0 => format!("{ghost}{ghost}A"),
+            _ if i == prime * scale && ghost == 0 => String::from("ZZZ"),
+            _ if i == prime * scale => format!("{ghost}{ghost}Z"),
+            i => format!("{ghost}N{i}"),
+        };
+        for i in 0..=prime * scale {
+            let next = name(if i == prime * scale { 1 } else { i + 1 });
+            nodes.push(format!("{} = ({next}, {next})", name(i)));
+        }
+    }
+    rng.shuffle(&mut nodes);
+    format!("{directions}\n\n") + &lines(nodes)
+}
+
+fn day09(rng: &mut Rng, size: usize) -> String {
+    // polynomials with small coefficients, so the sums still fit
+    lines((0..size).map(|_| {
+        let coefficients = (0..rng.between(1, 4))
+            .map(|_| rng.between(0, 6) as i64 - 3)
+            .collect::<Vec<_>>();
+        let values = (0..21).map(|x| {
+            let value = coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+            value.to_string()
+        });
+        values.collect::<Vec<_>>().join(" ")
+    }))
+}
+
+fn day10(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5);
+    let bottom = size - 1;
+    // comb shaped loop: along the top and right edge, then back along the bottom with teeth
+    let mut corners = vec![(0, 0), (bottom, 0), (bottom, bottom)];
+    let mut x = bottom;
+    while x >= 3 {
+        let top = rng.between(1, bottom - 1);
+        corners.extend([(x - 1, bottom), (x - 1, top), (x - 2, top), (x - 2, bottom)]);
+        x -= 2;
+    }
+    corners.push((0, bottom));
+    let mut tiles = vec![(0, 0)];
+    for &(cx, cy) in corners.iter().skip(1).chain([&(0, 0)]) {
+        while tiles[tiles.len() - 1] != (cx, cy) {
+            let (tx, ty) = tiles[tiles.len() - 1];
+            let next = match (cx.cmp(&tx), cy.cmp(&ty)) {
+                (std::cmp::Ordering::Greater, _) => (tx + 1, ty),
+                (std::cmp::Ordering::Less, _) => (tx - 1, ty),
+                (_, std::cmp::Ordering::Greater) => (tx, ty + 1),
+                _ => (tx, ty - 1),
+            };
+            tiles.push(next);
+        }
+    }
+    tiles.pop();
+    let mut grid = (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| rng.pick(&['.', '.', '.', '|', '-', 'L', 'J', '7', 'F']))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    for (i, &(x, y)) in tiles.iter().enumerate() {
+        let prev = tiles[(i + tiles.len() - 1) % tiles.len()];
+        let next = tiles[(i + 1) % tiles.len()];
+        let connects = |(nx, ny): (usize, usize)| (nx as i64 - x as i64, ny as i64 - y as i64);
+        let mut ends = [connects(prev), connects(next)];
+        ends.sort_unstable();
+        grid[y][x] = match ends {
+            [(0, -1), (0, 1)] => '|',
+            [(-1, 0), (1, 0)] => '-',
+            [(0, -1), (1, 0)] => 'L',
+            [(-1, 0), (0, -1)] => 'J',
+            [(-1, 0), (0, 1)] => '7',
+            _ => 'F',
+        };
+    }
+    grid[0][0] = 'S';
+    lines(grid.into_iter().map(|row| row.into_iter().collect()))
+}
+
+fn day12(rng: &mut Rng, size: usize) -> String {
+    lines((0..size).map(|_| {
+        // random springs, the groups are read from them before some are hidden
+        let mut springs = (0..rng.between(5, 20))
+            .map(|_| if rng.chance(50) { b'#' } else { b'.' })
+            .collect::<Vec<_>>();
+        let at = rng.below(springs.len());
+        springs[at] = b'#';
+        let groups = springs
+            .split(|&c| c == b'.')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string());
+        let groups = groups.collect::<Vec<_>>().join(",");
+        for spring in springs.iter_mut() {
+            if rng.chance(40) {
+                *spring = b'?';
+            }
+        }
+        format!("{} {groups}", String::from_utf8(springs).unwrap())
+    }))
+}
+
+fn day13(rng: &mut Rng, size: usize) -> String {
+    let patterns = (0..size).map(|_| {
+        let (width, height) = (rng.between(5, 17), rng.between(5, 17));
+        let mut pattern = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| rng.pick(&['.', '#']))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // mirror one side, so there is a reflection for part 1
+        if rng.chance(50) {
+            let mirror = rng.between(1, height - 1);
+            for y in mirror..height.min(2 * mirror) {
+                pattern[y] = pattern[2 * mirror - 1 - y].clone();
+            }
+        } else {
+            let mirror = rng.between(1, width - 1);
+            for row in pattern.iter_mut() {
+                for x in mirror..width.min(2 * mirror) {
+                    row[x] = row[2 * mirror - 1 - x];
+                }
+            }
+        }
+        lines(pattern.into_iter().map(|row| row.into_iter().collect()))
+    });
+    patterns.collect::<Vec<_>>().join("\n")
+}
+
+fn day15(rng: &mut Rng, size: usize) -> String {
+    let labels = (0..(size / 8).max(1))
+        .map(|_| {
+            (0..rng.between(2, 6))
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    let steps = (0..size).map(|_| {
+        let label = &labels[rng.below(labels.len())];
+        if rng.chance(40) {
+            format!("{label}-")
+        } else {
+            format!("{label}={}", rng.between(1, 9))
+        }
+    });
+    steps.collect::<Vec<_>>().join(",") + "\n"
+}
+
+fn day18(rng: &mut Rng, size: usize) -> String {
+    // histogram shaped loop: right along the bottom and back over the columns
+    let columns = (0..size)
+        .map(|_| (rng.between(1, 10), rng.between(1, 20)))
+        .collect::<Vec<_>>();
+    let mut plan = columns
+        .iter()
+        .map(|&(width, _)| ('R', width))
+        .collect::<Vec<_>>();
+    plan.push(('U', columns[size - 1].1));
+    for (i, &(width, height)) in columns.iter().enumerate().rev() {
+        plan.push(('L', width));
+        let next = if i > 0 { columns[i - 1].1 } else { 0 };
+        if height > next {
+            plan.push(('D', height - next));
+        } else if height < next {
+            plan.push(('U', next - height));
+        }
+    }
+    // the color describes the same shape scaled up
+    let scale = rng.between(1000, 50000);
+    lines(plan.into_iter().map(|(dir, dist)| {
+        let code = "RDLU".find(dir).unwrap();
+        format!("{dir} {dist} (#{:05x}{code})", dist * scale)
+    }))
+}
+
+fn day19(rng: &mut Rng, size: usize) -> String {
+    let count = (size / 4).max(1);
+    // letters in base 26 and at least three of them, so no other workflow is called `in`
+    let name = |i: usize| {
+        if i == 0 {
+            return String::from("in");
+        }
+        let mut name = String::new();
+        let mut rest = i;
+        while rest > 0 || name.len() < 3 {
+            name.push(char::from(b'a' + (rest % 26) as u8));
+            rest /= 26;
+        }
+        name
+    };
+    // workflows only send parts to later ones, so there are no cycles
+    let destination = |rng: &mut Rng, i: usize| {
+        if i + 1 < count && rng.chance(60) {
+            name(rng.between(i + 1, count - 1))
+        } else {
+            String::from(rng.pick(&["A", "R"]))
+        }
+    };
+    let workflows = (0..count).map(|i| {
+        let checks = (0..rng.between(1, 4)).map(|_| {
+            let field = rng.pick(&['x', 'm', 'a', 's']);
+            let cond = rng.pick(&['<', '>']);
+            let value = rng.between(1, 4000);
+            format!("{field}{cond}{value}:{},", destination(rng, i))
+        });
+        let checks = checks.collect::<String>();
+        format!("{}{{{checks}{}}}", name(i), destination(rng, i))
+    });
+    let workflows = lines(workflows.collect::<Vec<_>>());
+    let parts = (0..size).map(|_| {
+        let [x, m, a, s] = [(); 4].map(|_| rng.between(1, 4000));
+        format!("{{x={x},m={m},a={a},s={s}}}")
+    });
+    workflows + "\n" + &lines(parts)
+}
+
+fn day20(rng: &mut Rng, size: usize) -> String {
+    // at least three letters, so no module is called `rx`
+    let name = |i: usize| {
+        let mut name = String::new();
+        let mut rest = i;
+        while rest > 0 || name.len() < 3 {
+            name.push(char::from(b'a' + (rest % 26) as u8));
+            rest /= 26;
+        }
+        name
+    };
+    let mut modules = vec![];
+    let mut starts = vec![];
+    // like the real input: each counter is a chain of flip-flops and a conjunction that resets
+    // it when it reaches the value of the flip-flops it listens to, then the inverter after
+    // the conjunction sends a high pulse to the conjunction feeding rx
+    for counter in 0..size {
+        let bit = |b: usize| name(counter * 14 + b);
+        let (conjunction, inverter) = (name(counter * 14 + 12), name(counter * 14 + 13));
+        let value = rng.between(2048, 4095) | 1;
+        let mut resets = vec![bit(0)];
+        for b in 0..12 {
+            let mut outputs = vec![];
+            if b < 11 {
+                outputs.push(bit(b + 1));
+            }
+            if value >> b & 1 == 1 {
+                outputs.push(conjunction.clone());
+            } else {
+                resets.push(bit(b));
+            }
+            rng.shuffle(&mut outputs);
+            modules.push(format!("%{} -> {}", bit(b), outputs.join(", ")));
+        }
+        resets.push(inverter.clone());
+        rng.shuffle(&mut resets);
+        modules.push(format!("&{conjunction} -> {}", resets.join(", ")));
+        modules.push(format!("&{inverter} -> hb"));
+        starts.push(bit(0));
+    }
+    modules.push(String::from("&hb -> rx"));
+    modules.push(format!("broadcaster -> {}", starts.join(", ")));
+    rng.shuffle(&mut modules);
+    lines(modules)
+}
+
+fn day21(rng: &mut Rng, size: usize) -> String {
+    // odd and with the start in the middle, like the real input
+    let size = size.max(5) | 1;
+    let middle = size / 2;
+    lines((0..size).map(|y| {
+        let row = (0..size).map(|x| {
+            if (x, y) == (middle, middle) {
+                'S'
+            } else if x == middle || y == middle || x % (size - 1) == 0 || y % (size - 1) == 0 {
+                // the middle row and column and the border are free like in the real input
+                '.'
+            } else if rng.chance(15) {
+                '#'
+            } else {
+                '.'
+            }
+        });
+        row.collect()
+    }))
+}
+
+fn day22(rng: &mut Rng, size: usize) -> String {
+    // every brick starts above the previous one, so none of them overlap
+    let mut z = 1;
+    lines((0..size).map(|_| {
+        let (x, y) = (rng.below(10), rng.below(10));
+        let length = rng.below(4);
+        let end = match rng.below(3) {
+            0 => (x + length, y, z),
+            1 => (x, y + length, z),
+            _ => (x, y, z + length),
+        };
+        let brick = format!("{x},{y},{z}~{},{},{}", end.0, end.1, end.2);
+        z = end.2 + rng.between(1, 3);
+        brick
+    }))
+}
+
+fn day23(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(7);
+    // corridors along the lattice lines, the hike can't get longer than all of them
+    let count = ((size - 1) / 4).clamp(1, 6);
+    let lines = (0..count)
+        .map(|i| 1 + i * (size - 3) / (count - 1).max(1))
+        .collect::<Vec<_>>();
+    let (first, last) = (lines[0], lines[count - 1]);
+    let mut map = vec![vec!['#'; size]; size];
+    for &line in &lines {
+        map[line][first..=last].fill('.');
+        for row in &mut map[first..=last] {
+            row[line] = '.';
+        }
+    }
+    for row in map.iter_mut().take(first) {
+        row[first] = '.';
+    }
+    for row in map.iter_mut().skip(last) {
+        row[last] = '.';
+    }
+    // close some corridors and put slopes around the junctions
+    for (i, pair) in lines.windows(2).enumerate() {
+        let (a, b) = (pair[0], pair[1]);
+        for &line in &lines {
+            for (x, y, slope) in [(a, line, '>'), (line, a, 'v')] {
+                let horizontal = slope == '>';
+                let at = |offset: usize| {
+                    if horizontal {
+                        (x + offset, y)
+                    } else {
+                        (x, y + offset)
+                    }
+                };
+                if i > 0 && rng.chance(15) {
+                    let (x, y) = at((b - a) / 2);
+                    map[y][x] = '#';
+                }
+                for offset in [1, b - a - 1] {
+                    let (x, y) = at(offset);
+                    if map[y][x] == '.' {
+                        map[y][x] = slope;
+                    }
+                }
+            }
+        }
+    }
+    lines_of(map)
+}
+
+fn lines_of(map: Vec<Vec<char>>) -> String {
+    lines(map.into_iter().map(|row| row.into_iter().collect()))
+}
+
+fn day24(rng: &mut Rng, size: usize) -> String {
+    // throw the rock first and put every hailstone where it gets hit
+    let coordinate = |rng: &mut Rng| rng.between(100, 300) as i64 * 1_000_000_000_000;
+    let speed = |rng: &mut Rng| rng.between(0, 600) as i64 - 300;
+    let position = [0; 3].map(|_| coordinate(rng));
+    let velocity = [0; 3].map(|_| speed(rng));
+    lines((0..size.max(3)).map(|_| {
+        let time = rng.between(100, 1000) as i64 * 1_000_000_000;
+        let mut hailstone = [0; 3].map(|_| speed(rng));
+        if hailstone == velocity {
+            hailstone[0] += 1;
+        }
+        let start = [0, 1, 2].map(|i| position[i] + time * (velocity[i] - hailstone[i]));
+        format!(
+            "{}, {}, {} @ {}, {}, {}",
+            start[0], start[1], start[2], hailstone[0], hailstone[1], hailstone[2]
+        )
+    }))
+}
+
+fn day25(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(10);
+    let mut names = (0..size)
+        .map(|i| {
+            let letter = |n: usize| char::from(b'a' + (n % 26) as u8);
+            format!("{}{}{}", letter(i / 676), letter(i / 26), letter(i))
+        })
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut names);
+    let groups = [0..size / 2, size / 2..size];
+    let mut wires = vec![];
+    for group in &groups {
+        // every component is wired to the next two, so no three wires split a group
+        let (start, len) = (group.start, group.len());
+        for i in 0..len {
+            for step in [1, 2] {
+                wires.push((start + i, start + (i + step) % len));
+            }
+            if rng.chance(30) {
+                wires.push((start + i, start + rng.below(len)));
+            }
+        }
+    }
+    for _ in 0..3 {
+        wires.push((rng.below(size / 2), rng.between(size / 2, size - 1)));
+    }
+    let mut connections = vec![vec![]; size];
+    for (a, b) in wires {
+        if a != b {
+            connections[a].push(names[b].clone());
+        }
+    }
+    lines(
+        connections
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, others)| {
+                (!others.is_empty()).then(|| format!("{}: {}", names[i], others.join(" ")))
+            }),
+    )
+}
@@ -0,0 +1,169 @@
+use crate::types::Position;
+
+/// Orientation of a polygon in the puzzle coordinate system (y pointing down).
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+    Degenerate,
+}
+
+/// Where a point lies relative to a polygon.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Twice the signed area of the polygon given by its vertices (shoelace formula).
+/// The polygon is closed implicitly, the first vertex must not be repeated at the end.
+pub fn double_signed_area(vertices: &[Position]) -> i64 {
+    let mut area = 0;
+    for (i, a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        area += a.x * b.y - a.y * b.x;
+    }
+    area
+}
+
+/// Absolute area of the polygon, rounded down for polygons with half integer area.
+pub fn area(vertices: &[Position]) -> i64 {
+    double_signed_area(vertices).abs() / 2
+}
+
+/// Number of lattice points on the edges of the polygon.
+pub fn boundary_points(vertices: &[Position]) -> i64 {
+    let mut points = 0;
+    for (i, a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        points += num::integer::gcd((b.x - a.x).abs(), (b.y - a.y).abs());
+    }
+    points
+}
+
+/// Number of lattice points strictly inside the polygon, using Pick's theorem.
+pub fn interior_points(vertices: &[Position]) -> i64 {
+    // A = I + B/2 - 1  =>  2I = 2A - B + 2
+    (double_signed_area(vertices).abs() - boundary_points(vertices) + 2) / 2
+}
+
+/// Number of lattice points inside or on the edges of the polygon.
+pub fn enclosed_points(vertices: &[Position]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+pub fn winding(vertices: &[Position]) -> Winding {
+    // with y pointing down a positive shoelace sum means the polygon turns clockwise on screen
+    match double_signed_area(vertices).signum() {
+        1 => Winding::Clockwise,
+        -1 => Winding::CounterClockwise,
+        _ => Winding::Degenerate,
+    }
+}
+
+/// Locate a point relative to the polygon by casting a ray in positive x direction.
+pub fn locate(vertices: &[Position], point: Position) -> Location {
+    let mut inside = false;
+    for (i, a) in vertices.iter().enumerate() {
+        let b = vertices[(i + 1) % vertices.len()];
+        if on_segment(*a, b, point) {
+            return Location::Boundary;
+        }
+        // only count edges crossing the ray, half open in y to count shared vertices once
+        if (a.y > point.y) != (b.y > point.y) {
+            // x coordinate of the crossing compared without division
+            let lhs = (point.x - a.x) * (b.y - a.y);
+            let rhs = (b.x - a.x) * (point.y - a.y);
+            if (lhs < rhs) == (b.y > a.y) {
+                inside = !inside;
+            }
+        }
+    }
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+pub fn contains(vertices: &[Position], point: Position) -> bool {
+    locate(vertices, point) == Location::Inside
+}
+
+fn on_segment(a: Position, b: Position, p: Position) -> bool {
+    let cross = (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x);
+    cross == 0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn square(size: i64) -> Vec<Position> {
+        vec![
+            Position::new(0, 0),
+            Position::new(size, 0),
+            Position::new(size, size),
+            Position::new(0, size),
+        ]
+    }
+
+    #[test]
+    fn square_counts() {
+        let square = square(4);
+        assert_eq!(double_signed_area(&square), 32);
+        assert_eq!(area(&square), 16);
+        assert_eq!(boundary_points(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+        assert_eq!(enclosed_points(&square), 25);
+    }
+
+    #[test]
+    fn triangle_counts() {
+        let triangle = [
+            Position::new(0, 0),
+            Position::new(4, 0),
+            Position::new(0, 3),
+        ];
+        assert_eq!(area(&triangle), 6);
+        // edges with 4, 3 and gcd(4, 3) = 1 lattice steps
+        assert_eq!(boundary_points(&triangle), 8);
+        assert_eq!(interior_points(&triangle), 3);
+    }
+
+    #[test]
+    fn winding_direction() {
+        let mut square = square(2);
+        assert_eq!(winding(&square), Winding::Clockwise);
+        square.reverse();
+        assert_eq!(winding(&square), Winding::CounterClockwise);
+        let line = [Position::new(0, 0), Position::new(3, 0)];
+        assert_eq!(winding(&line), Winding::Degenerate);
+    }
+
+    #[test]
+    fn point_location() {
+        // L shaped polygon
+        let poly = [
+            Position::new(0, 0),
+            Position::new(2, 0),
+            Position::new(2, 2),
+            Position::new(4, 2),
+            Position::new(4, 4),
+            Position::new(0, 4),
+        ];
+        assert_eq!(locate(&poly, Position::new(1, 1)), Location::Inside);
+        assert_eq!(locate(&poly, Position::new(3, 3)), Location::Inside);
+        assert_eq!(locate(&poly, Position::new(3, 1)), Location::Outside);
+        assert_eq!(locate(&poly, Position::new(2, 1)), Location::Boundary);
+        assert_eq!(locate(&poly, Position::new(4, 4)), Location::Boundary);
+        assert_eq!(locate(&poly, Position::new(-1, 2)), Location::Outside);
+        assert!(contains(&poly, Position::new(1, 3)));
+        assert!(!contains(&poly, Position::new(0, 3)));
+    }
+}
@@ -1,12 +1,93 @@
+pub mod cache;
 mod days;
+pub mod explain;
+mod explore;
+pub mod ffi;
+pub mod generate;
+pub mod geometry;
+pub mod parse;
+#[cfg(feature = "reference")]
+pub mod reference;
+pub mod render;
+pub mod serve;
 mod types;
 mod util;
 
 use std::time::{Duration, Instant};
 
-pub use days::DAY_COUNT;
+use cache::AnswerCache;
+pub use days::{calibration_with, digit_words, Area, Bag, ColorStats, Game, Warning, DAY_COUNT};
+use parse::ParseError;
 use rayon::prelude::*;
-pub use types::Task;
+pub use types::{Coord, Direction, Direction3, Position, Position3, Task, Timings};
+pub use util::{embedded_input, find_input, read_input, Normalize, TrailingNewline};
+
+/// Solve a day for the given input, which is normalized as the day expects it.
+/// Panics on malformed input, see [`try_solve`].
+pub fn solve(day: usize, input: &str, task: Task) -> (String, String) {
+    let (res1, res2, _) = solve_timed(day, input, task);
+    (res1, res2)
+}
+
+/// Like [`solve`], but also returns the time spent in each phase.
+pub fn solve_timed(day: usize, input: &str, task: Task) -> (String, String, Timings) {
+    try_solve(day, input, task).unwrap_or_else(|e| panic!("day {day}: {e}"))
+}
+
+/// Like [`solve_timed`], but returns an error instead of panicking if the input can't be parsed.
+pub fn try_solve(
+    day: usize,
+    input: &str,
+    task: Task,
+) -> Result<(String, String, Timings), ParseError> {
+    days::solve(day, input, &RunOptions::new(false, task))
+}
+
+/// Trace of how the answers of a day are reached, None if the day can't explain itself.
+pub fn explain(day: usize, input: &str) -> Result<Option<explain::Explanation>, ParseError> {
+    assert!((1..=DAY_COUNT).contains(&day), "invalid day {day}");
+    days::explain(day, input)
+}
+
+/// Interactively explore the parsed input of a day, see `help` for the available commands.
+pub fn explore(
+    day: usize,
+    test: bool,
+    commands: &mut impl std::io::BufRead,
+    out: &mut impl std::io::Write,
+) -> std::io::Result<()> {
+    assert!((1..=DAY_COUNT).contains(&day), "invalid day {day}");
+    days::explore(day, util::read_input(day, test), test, commands, out)
+}
+
+/// How [`calc_day_with`], [`run_serial_with`] and [`run_parallel_with`] solve the days.
+#[derive(Clone, Copy)]
+pub struct RunOptions<'a> {
+    /// use the test inputs and print debug output
+    pub test: bool,
+    pub task: Task,
+    /// print the parsing and solving times of every day
+    pub print_times: bool,
+    /// answer unchanged inputs from the cache and store new answers in it
+    pub cache: Option<&'a AnswerCache>,
+    /// replaces the bag of day 2 part 1
+    pub bag: Option<&'a Bag>,
+    /// replaces the test area of day 24 part 1
+    pub area: Option<Area>,
+}
+
+impl RunOptions<'_> {
+    pub fn new(test: bool, task: Task) -> Self {
+        Self {
+            test,
+            task,
+            print_times: false,
+            cache: None,
+            bag: None,
+            area: None,
+        }
+    }
+}
 
 pub fn calc_day(
     day: usize,
@@ -17,13 +98,47 @@ pub fn calc_day(
     task: Task,
     print_times: bool,
 ) {
+    let options = RunOptions {
+        print_times,
+        ..RunOptions::new(test, task)
+    };
+    calc_day_with(day, result1, result2, time, &options);
+}
+
+/// Solve a day, with a cache unchanged inputs are answered with the stored answers and time
+/// without solving them again.
+pub fn calc_day_with(
+    day: usize,
+    result1: &mut String,
+    result2: &mut String,
+    time: &mut Duration,
+    options: &RunOptions,
+) {
+    let RunOptions {
+        test, task, cache, ..
+    } = *options;
     if test {
         println!("\n##################\ncalculating day {day} \n##################\n");
     }
     let input = util::read_input(day, test);
-    let start = Instant::now();
-    let (res1, res2) = days::solve(day, &input, test, task, print_times);
-    *time = Instant::now().duration_since(start);
+    let fingerprint = cache.map(|_| cache::fingerprint(day, &input, options));
+    let cached = cache
+        .zip(fingerprint)
+        .and_then(|(cache, fingerprint)| cache.get(day, task, fingerprint));
+    let (res1, res2, elapsed) = match cached {
+        Some(cached) => cached,
+        None => {
+            let start = Instant::now();
+            let (res1, res2, _) =
+                days::solve(day, &input, options).unwrap_or_else(|e| panic!("day {day}: {e}"));
+            let elapsed = start.elapsed();
+            if let Some((cache, fingerprint)) = cache.zip(fingerprint) {
+                cache.insert(day, task, fingerprint, (&res1, &res2), elapsed);
+            }
+            (res1, res2, elapsed)
+        }
+    };
+    *time = elapsed;
     *result1 = res1;
     *result2 = res2;
 }
@@ -35,16 +150,28 @@ pub fn run_serial(
     times: &mut [Duration],
     test: bool,
     task: Task,
+) {
+    let options = RunOptions {
+        print_times: true,
+        ..RunOptions::new(test, task)
+    };
+    run_serial_with(days, results1, results2, times, &options);
+}
+
+pub fn run_serial_with(
+    days: &[usize],
+    results1: &mut [String],
+    results2: &mut [String],
+    times: &mut [Duration],
+    options: &RunOptions,
 ) {
     for (i, day) in days.iter().enumerate() {
-        calc_day(
+        calc_day_with(
             *day,
             &mut results1[i],
             &mut results2[i],
             &mut times[i],
-            test,
-            task,
-            true,
+            options,
         );
     }
 }
@@ -56,12 +183,26 @@ pub fn run_parallel(
     times: &mut Vec<Duration>,
     test: bool,
     task: Task,
+) {
+    let options = RunOptions {
+        print_times: true,
+        ..RunOptions::new(test, task)
+    };
+    run_parallel_with(days, results1, results2, times, &options);
+}
+
+pub fn run_parallel_with(
+    days: &[usize],
+    results1: &mut Vec<String>,
+    results2: &mut Vec<String>,
+    times: &mut Vec<Duration>,
+    options: &RunOptions,
 ) {
     days.par_iter()
         .zip(results1)
         .zip(results2)
         .zip(times)
         .for_each(|(((day, result1), result2), time)| {
-            calc_day(*day, result1, result2, time, test, task, true);
+            calc_day_with(*day, result1, result2, time, options);
         });
 }
@@ -1,10 +1,23 @@
-use aoc23::{run_parallel, run_serial, Task, DAY_COUNT};
-use clap::Parser;
+use aoc23::{
+    cache::{self, AnswerCache, CacheMode},
+    embedded_input, explore,
+    generate::generate,
+    render::{self, Backend, RenderConfig},
+    run_parallel_with, run_serial_with,
+    serve::serve,
+    Area, Bag, RunOptions, Task, DAY_COUNT,
+};
+use clap::{Parser, Subcommand};
 use std::{
+    io,
+    net::TcpListener,
+    path::PathBuf,
     time::{Duration, Instant},
     vec,
 };
 
+#[cfg(test)]
+mod properties;
 #[cfg(test)]
 mod test;
 
@@ -17,6 +30,8 @@ fn cap_length(msg: &str, length: usize) -> &str {
 
 #[derive(Parser)]
 struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
     #[clap(short, long, default_value = "false")]
     test: bool,
     #[clap(short, long, default_value = "0")]
@@ -27,11 +42,225 @@ struct Args {
     parallel: bool,
     #[clap(long)]
     day_string: Option<String>,
+    /// list the inputs embedded with the `embed-inputs` feature
+    #[clap(long, default_value = "false")]
+    list_inputs: bool,
+    /// answer unchanged inputs from the cache, see the `cache` command
+    #[clap(long, default_value = "false", conflicts_with = "no_cache")]
+    cached: bool,
+    /// solve even if the answers are cached and update the cache
+    #[clap(long, default_value = "false")]
+    no_cache: bool,
+    /// show how the answers are reached instead of only the results
+    #[clap(long, default_value = "false")]
+    explain: bool,
+    /// compare days with a brute force reference on small generated inputs, needs the
+    /// `reference` feature
+    #[clap(long, default_value = "false")]
+    cross_check: bool,
+    /// write frames of days supporting visualization to this directory
+    #[clap(long)]
+    render: Option<PathBuf>,
+    /// ascii, ansi, ppm or png
+    #[clap(long, default_value = "ascii")]
+    render_format: Backend,
+    /// render every nth step instead of only the final state
+    #[clap(long)]
+    render_every: Option<usize>,
+    /// cubes in the bag of day 2 part 1, e.g. "12 red, 13 green, 14 blue"
+    #[clap(long)]
+    bag: Option<Bag>,
+    /// lowest and highest x and y of the day 24 part 1 test area, e.g. "7,27" for the example
+    #[clap(long)]
+    area: Option<Area>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// solve inputs posted to a local http server
+    Serve {
+        #[clap(long, default_value = "127.0.0.1:8023")]
+        addr: String,
+    },
+    /// print a synthetic input of the given size, see `aoc23::generate` for what size means
+    Generate {
+        day: usize,
+        #[clap(short, long, default_value = "100")]
+        size: usize,
+        #[clap(long, default_value = "1")]
+        seed: u64,
+    },
+    /// list the cached answers
+    Cache {
+        /// remove entries of inputs or solvers that changed
+        #[clap(long, default_value = "false")]
+        prune: bool,
+        /// remove all entries
+        #[clap(long, default_value = "false")]
+        clear: bool,
+    },
+    /// parse the input of a day once and run commands on it
+    Explore {
+        day: usize,
+        #[clap(short, long, default_value = "false")]
+        test: bool,
+    },
+}
+
+fn list_inputs() {
+    if !cfg!(feature = "embed-inputs") {
+        println!("built without the `embed-inputs` feature, inputs are read from ./inputs");
+        return;
+    }
+    println!("day | real | test");
+    println!("--: | :--: | :--:");
+    let mark = |input: Option<&str>| if input.is_some() { "x" } else { " " };
+    for day in 1..=DAY_COUNT {
+        println!(
+            "{day: >3} | {: ^4} | {: ^4}",
+            mark(embedded_input(day, false)),
+            mark(embedded_input(day, true))
+        );
+    }
+}
+
+fn manage_cache(prune: bool, clear: bool) -> io::Result<()> {
+    let cache = AnswerCache::load(cache::DEFAULT_PATH, CacheMode::Use)?;
+    if clear {
+        cache.clear();
+    } else if prune {
+        println!("removed {} stale entries", cache.prune());
+    }
+    if clear || prune {
+        return cache.save();
+    }
+    println!("day | task | fingerprint      | result 1        | result 2        | time");
+    println!("--: | :--: | :--------------: | :-------------: | :-------------: | --------:");
+    for entry in cache.entries() {
+        println!(
+            "{: >3} | {: ^4} | {:016x} | {: <15} | {: <15} | {: >9.2?}",
+            entry.day,
+            entry.task,
+            entry.fingerprint,
+            cap_length(&entry.result1, 15),
+            cap_length(&entry.result2, 15),
+            entry.time
+        );
+    }
+    Ok(())
+}
+
+/// Size and number of seeds of the generated inputs for `--cross-check`, small enough for the
+/// references to brute force most parts.
+#[cfg(feature = "reference")]
+const CHECK_SIZE: usize = 10;
+#[cfg(feature = "reference")]
+const CHECK_SEEDS: u64 = 3;
+
+#[cfg(feature = "reference")]
+fn cross_check(day: usize) {
+    use aoc23::reference::{self, Check};
+    let days = if day == 0 {
+        reference::DAYS.to_vec()
+    } else {
+        vec![day]
+    };
+    let mut disagreements = 0;
+    for day in days {
+        if !reference::DAYS.contains(&day) {
+            println!("day {day}: no reference solver");
+            continue;
+        }
+        for seed in 0..CHECK_SEEDS {
+            let input = generate(day, CHECK_SIZE, seed);
+            let checks = match reference::cross_check(day, &input) {
+                Ok(checks) => checks,
+                Err(e) => {
+                    println!("day {day} seed {seed}: could not parse input: {e}");
+                    continue;
+                }
+            };
+            for (part, check) in checks.into_iter().enumerate() {
+                let run = format!("day {day} seed {seed} part {}", part + 1);
+                match check {
+                    Check::Agree(answer) => println!("{run}: {answer}"),
+                    Check::Skipped(answer) => {
+                        println!("{run}: {answer}, input too large for the reference")
+                    }
+                    Check::Disagree {
+                        optimized,
+                        reference,
+                    } => {
+                        disagreements += 1;
+                        println!("{run}: {optimized}, but the reference says {reference}");
+                    }
+                }
+            }
+        }
+    }
+    if disagreements > 0 {
+        println!("{disagreements} disagreements");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(not(feature = "reference"))]
+fn cross_check(_day: usize) {
+    println!("built without the `reference` feature, run with `--features reference`");
+}
+
+/// Lines skipped by day 1 change its answers, so they are reported even outside of test mode.
+fn warn_day1(test: bool, task: Task) {
+    let input = aoc23::read_input(1, test);
+    let english = aoc23::digit_words("english").unwrap();
+    let parts = [(1, &[][..]), (2, english)];
+    for (part, words) in parts {
+        if matches!((part, task), (1, Task::Two) | (2, Task::One)) {
+            continue;
+        }
+        for warning in aoc23::calibration_with(&input, words).1 {
+            eprintln!("warning: day 1 part {part}: {warning}");
+        }
+    }
 }
 
 fn main() {
     // parse command line arguments
     let args: Args = Args::parse();
+    match args.command {
+        Some(Command::Serve { addr }) => {
+            let listener = TcpListener::bind(&addr).expect("could not bind address");
+            println!("serving on http://{addr}");
+            serve(listener).expect("server failed");
+            return;
+        }
+        Some(Command::Generate { day, size, seed }) => {
+            print!("{}", generate(day, size, seed));
+            return;
+        }
+        Some(Command::Cache { prune, clear }) => {
+            manage_cache(prune, clear).expect("could not access the cache");
+            return;
+        }
+        Some(Command::Explore { day, test }) => {
+            println!("exploring day {day}, type 'help' for a list of commands");
+            explore(day, test, &mut io::stdin().lock(), &mut io::stdout()).unwrap();
+            return;
+        }
+        None => {}
+    }
+    if args.list_inputs {
+        list_inputs();
+        return;
+    }
+    if let Some(dir) = args.render {
+        render::init(RenderConfig {
+            dir,
+            backend: args.render_format,
+            every: args.render_every,
+        })
+        .expect("could not create render directory");
+    }
     let test = args.test;
     let task = if args.task == 1 {
         Task::One
@@ -44,27 +273,73 @@ fn main() {
         Some(day) => day.replace("day", "").parse::<usize>().unwrap_or(args.day),
         None => args.day,
     };
+    if args.cross_check {
+        cross_check(day);
+        return;
+    }
     let parallel = args.parallel;
 
     let days = if day == 0 {
-        (1..=DAY_COUNT).collect::<Vec<usize>>()
+        // not every day comes with its input, running all days skips the missing ones
+        let (days, missing) = (1..=DAY_COUNT)
+            .partition::<Vec<usize>, _>(|&day| aoc23::find_input(day, test).is_some());
+        if !missing.is_empty() {
+            println!("Skipping days without an input: {missing:?}");
+        }
+        days
     } else {
         vec![day]
     };
 
+    if args.explain {
+        for &day in &days {
+            match aoc23::explain(day, &aoc23::read_input(day, test)) {
+                Ok(Some(explanation)) => print!("## day {day}\n\n{explanation}"),
+                // listing every day without an explanation would only be noise
+                Ok(None) if days.len() > 1 => {}
+                Ok(None) => println!("day {day} can't explain its answers yet"),
+                Err(e) => println!("day {day}: could not parse input: {e}"),
+            }
+        }
+        return;
+    }
     println!("Calculating days: {days:?}");
 
     let mut results1 = vec![String::new(); days.len()];
     let mut results2 = vec![String::new(); days.len()];
     let mut times = vec![Duration::new(0, 0); days.len()];
 
+    let cache_mode = if args.cached {
+        Some(CacheMode::Use)
+    } else if args.no_cache {
+        Some(CacheMode::Refresh)
+    } else {
+        None
+    };
+    let cache = cache_mode.map(|mode| {
+        AnswerCache::load(cache::DEFAULT_PATH, mode).expect("could not read the cache")
+    });
+
+    let options = RunOptions {
+        print_times: true,
+        cache: cache.as_ref(),
+        bag: args.bag.as_ref(),
+        area: args.area,
+        ..RunOptions::new(test, task)
+    };
     let start = Instant::now();
     if parallel {
-        run_parallel(&days, &mut results1, &mut results2, &mut times, test, task);
+        run_parallel_with(&days, &mut results1, &mut results2, &mut times, &options);
     } else {
-        run_serial(&days, &mut results1, &mut results2, &mut times, test, task);
+        run_serial_with(&days, &mut results1, &mut results2, &mut times, &options);
     }
     let overall = Instant::now().duration_since(start);
+    if let Some(cache) = cache {
+        cache.save().expect("could not write the cache");
+    }
+    if days.contains(&1) {
+        warn_day1(test, task);
+    }
 
     let mut results: String = "## Results:\n".into();
     results += "day | result 1        | result 2        | time      | % overall \n";
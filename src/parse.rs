@@ -0,0 +1,210 @@
+use std::{fmt::Display, str::FromStr};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, newline, none_of, one_of, space0, space1},
+    combinator::{map_opt, map_res, opt, recognize},
+    error::{Error, ErrorKind},
+    multi::{many1, separated_list1},
+    sequence::{pair, preceded, separated_pair},
+    IResult, Parser,
+};
+
+/// Error of a failed parse with the position where parsing stopped.
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "parse error at line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl ParseError {
+    /// Error at the position where `rest` starts in `input`.
+    pub fn new(input: &str, rest: &str, message: impl Into<String>) -> Self {
+        let consumed = &input[..input.len() - rest.len()];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.len() - consumed.rfind('\n').map_or(0, |i| i + 1) + 1;
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+/// Run `parser` on the whole input. Trailing newlines are ignored, any other remaining
+/// input is reported as an error.
+pub fn parse_all<'a, T>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, T, Error<&'a str>>,
+) -> Result<T, ParseError> {
+    match parser.parse(input) {
+        Ok((rest, parsed)) if rest.trim_end_matches('\n').is_empty() => Ok(parsed),
+        Ok((rest, _)) => {
+            let found = rest.lines().next().unwrap_or_default();
+            Err(ParseError::new(
+                input,
+                rest,
+                format!("unexpected input '{found}'"),
+            ))
+        }
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+            Err(ParseError::new(input, e.input, describe(input, &e)))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::new(input, "", "unexpected end of input")),
+    }
+}
+
+/// Message for a nom error, the errors of [`grid`] are spelled out.
+fn describe(input: &str, e: &Error<&str>) -> String {
+    let found = e.input.lines().next().unwrap_or_default();
+    match (e.code, found.chars().next()) {
+        (ErrorKind::Char, Some(c)) => format!("unexpected character '{c}'"),
+        (ErrorKind::LengthValue, _) => {
+            // the error points at the start of the row, the rows before have the right width
+            let before = &input[..input.len() - e.input.len()];
+            let previous = before.strip_suffix('\n').unwrap_or(before);
+            let width = previous
+                .rsplit('\n')
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .count();
+            format!("row has width {}, expected {width}", found.chars().count())
+        }
+        (code, _) => format!("expected {code:?}, found '{found}'"),
+    }
+}
+
+/// A signed or unsigned integer.
+pub fn number<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Numbers on a single line separated by spaces, e.g. `0 3 6 9 12 15`.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<&str, Vec<T>> {
+    separated_list1(space1, number)(input)
+}
+
+/// Numbers following a label, e.g. `Time:      7  15   30`.
+pub fn labeled_numbers<T: FromStr>(
+    label: &'static str,
+) -> impl FnMut(&str) -> IResult<&str, Vec<T>> {
+    move |input| preceded(pair(tag(label), space0), numbers)(input)
+}
+
+/// One item per line. Does not consume the newline after the last line, so blocks
+/// separated by blank lines can be parsed with [`blocks`].
+pub fn lines<'a, T>(
+    parser: impl Parser<&'a str, T, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(newline, parser)
+}
+
+/// Blocks separated by blank lines.
+pub fn blocks<'a, T>(
+    parser: impl Parser<&'a str, T, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    separated_list1(tag("\n\n"), parser)
+}
+
+/// A rectangular character grid, every character is converted with `cell`. A row that
+/// starts with a valid cell but has a different width or an invalid cell fails the whole grid.
+pub fn grid<'a, T>(
+    cell: impl Fn(char) -> Option<T> + Copy,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<T>>> {
+    move |input| {
+        let mut row = many1(map_opt(none_of("\n"), cell));
+        let (mut input, first) = row(input)?;
+        let width = first.len();
+        let mut rows = vec![first];
+        while let Ok((rest, next)) = preceded(newline, &mut row)(input) {
+            if !(rest.is_empty() || rest.starts_with('\n')) {
+                // the row stops at a character that is no cell
+                return Err(nom::Err::Failure(Error::new(rest, ErrorKind::Char)));
+            }
+            if next.len() != width {
+                let row_start = &input[1..];
+                return Err(nom::Err::Failure(Error::new(
+                    row_start,
+                    ErrorKind::LengthValue,
+                )));
+            }
+            rows.push(next);
+            input = rest;
+        }
+        Ok((input, rows))
+    }
+}
+
+/// A `key=value` pair with a custom separator.
+pub fn key_value<'a, K, V>(
+    key: impl Parser<&'a str, K, Error<&'a str>>,
+    separator: char,
+    value: impl Parser<&'a str, V, Error<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (K, V)> {
+    separated_pair(key, char(separator), value)
+}
+
+#[cfg(test)]
+mod test {
+    use nom::character::complete::alpha1;
+
+    use super::*;
+
+    #[test]
+    fn number_lists() {
+        assert_eq!(parse_all("-3 4  5\n", numbers::<i32>), Ok(vec![-3, 4, 5]));
+        assert_eq!(
+            parse_all("Time:  7 15\n", labeled_numbers::<u64>("Time:")),
+            Ok(vec![7, 15])
+        );
+        let parsed = parse_all("1 2\n3 4\n\n5\n", blocks(lines(numbers::<u8>)));
+        assert_eq!(
+            parsed,
+            Ok(vec![vec![vec![1, 2], vec![3, 4]], vec![vec![5]]])
+        );
+        // trailing newline is optional
+        assert_eq!(
+            parse_all("1 2\n3 4", lines(numbers::<u8>)),
+            parse_all("1 2\n3 4\n\n", lines(numbers::<u8>))
+        );
+    }
+
+    #[test]
+    fn grids_and_records() {
+        let parsed = parse_all(".#\n#.\n", grid(|c| Some(c == '#')));
+        assert_eq!(parsed, Ok(vec![vec![false, true], vec![true, false]]));
+        let parsed = parse_all(
+            "a=1,bc=2",
+            separated_list1(char(','), key_value(alpha1, '=', number::<u8>)),
+        );
+        assert_eq!(parsed, Ok(vec![("a", 1), ("bc", 2)]));
+    }
+
+    #[test]
+    fn error_position() {
+        let err = parse_all("1 2\n3 x\n", lines(numbers::<u8>)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = parse_all("ab\ncd\n", grid(|c| (c != 'd').then_some(c))).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.message, "unexpected character 'd'");
+        let err = parse_all("ab\ncd\ne\n", grid(Some)).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert_eq!(err.message, "row has width 1, expected 2");
+        let err = parse_all("ab\ncde\n", grid(Some)).unwrap_err();
+        assert_eq!(err.message, "row has width 3, expected 2");
+    }
+}
@@ -0,0 +1,956 @@
+//! Property tests on generated inputs. Every generator only produces structurally valid
+//! input, so the answers have to be deterministic and parsing must never fail. Days 5, 8,
+//! 12, 14 and 18 are compared with the solvers of [`aoc23::reference`], the other days
+//! without a reference are compared with the small brute forces below where one is feasible.
+
+use std::collections::{HashMap, HashSet};
+
+use aoc23::{generate::generate, reference, try_solve, Task};
+use proptest::{array::uniform3, collection::vec, prelude::*, sample::select};
+
+/// Solve twice and check that the answers are the same.
+fn answers(day: usize, input: &str, task: Task) -> (String, String) {
+    let solve = || {
+        let (res1, res2, _) = try_solve(day, input, task)
+            .unwrap_or_else(|e| panic!("valid input was rejected: {e}\n{input}"));
+        (res1, res2)
+    };
+    let first = solve();
+    assert_eq!(first, solve(), "answers are not deterministic for\n{input}");
+    first
+}
+
+/// Answers of the brute force solver in [`aoc23::reference`], parts outside of the task are
+/// empty like in [`answers`].
+fn reference_answers(day: usize, input: &str, task: Task) -> (String, String) {
+    let (res1, res2) = reference::solve(day, input, task);
+    let part = |res: Option<String>, skipped: bool| match res {
+        _ if skipped => String::new(),
+        Some(res) => res,
+        None => panic!("input is too large for the reference\n{input}"),
+    };
+    (
+        part(res1, matches!(task, Task::Two)),
+        part(res2, matches!(task, Task::One)),
+    )
+}
+
+fn join_lines(lines: impl IntoIterator<Item = String>) -> String {
+    lines.into_iter().map(|l| l + "\n").collect()
+}
+
+fn grid_string(grid: &[Vec<char>]) -> String {
+    join_lines(grid.iter().map(|row| row.iter().collect()))
+}
+
+/// A grid with the given range of widths and heights.
+fn grid<T: std::fmt::Debug + Clone>(
+    size: std::ops::Range<usize>,
+    cell: impl Strategy<Value = T> + Clone,
+) -> impl Strategy<Value = Vec<Vec<T>>> {
+    (size.clone(), size).prop_flat_map(move |(w, h)| vec(vec(cell.clone(), w), h))
+}
+
+const DIGIT_WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+fn day1_brute_force(input: &str) -> (u32, u32) {
+    let mut sums = (0, 0);
+    for line in input.lines() {
+        let digits = line
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .collect::<Vec<_>>();
+        sums.0 += digits[0] * 10 + digits[digits.len() - 1];
+        // digits and words at every position, words may overlap
+        let digits = (0..line.len())
+            .filter_map(|i| {
+                let rest = &line[i..];
+                rest.chars()
+                    .next()
+                    .and_then(|c| c.to_digit(10))
+                    .or_else(|| {
+                        let word = DIGIT_WORDS.iter().position(|w| rest.starts_with(w))?;
+                        Some(word as u32 + 1)
+                    })
+            })
+            .collect::<Vec<_>>();
+        sums.1 += digits[0] * 10 + digits[digits.len() - 1];
+    }
+    sums
+}
+
+type Draw = Vec<(u32, &'static str)>;
+
+fn day2_brute_force(games: &[Vec<Draw>]) -> (usize, u32) {
+    let mut sums = (0, 0);
+    for (i, game) in games.iter().enumerate() {
+        // cubes of the same color in one draw are shown together
+        let max = |color| {
+            let draws = game.iter().map(|draw| {
+                let counts = draw.iter().filter(|(_, c)| *c == color);
+                counts.map(|(n, _)| *n).sum::<u32>()
+            });
+            draws.max().unwrap_or(0)
+        };
+        let (red, green, blue) = (max("red"), max("green"), max("blue"));
+        // the bag has no cubes of other colors
+        if red <= 12 && green <= 13 && blue <= 14 && max("yellow") == 0 {
+            sums.0 += i + 1;
+        }
+        sums.1 += red * green * blue;
+    }
+    sums
+}
+
+fn day3_brute_force(grid: &[Vec<char>]) -> (u32, u64) {
+    // (row, first column, column after the number, value)
+    let mut numbers = vec![];
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let start = x;
+            while x < row.len() && row[x].is_ascii_digit() {
+                x += 1;
+            }
+            if x > start {
+                let value: u32 = row[start..x].iter().collect::<String>().parse().unwrap();
+                numbers.push((y, start, x, value));
+            } else {
+                x += 1;
+            }
+        }
+    }
+    let adjacent = |x: usize, y: usize| {
+        numbers
+            .iter()
+            .filter(move |(ny, start, end, _)| ny.abs_diff(y) <= 1 && x + 1 >= *start && x <= *end)
+            .map(|n| n.3)
+    };
+    let mut parts = HashSet::new();
+    let mut ratios = 0;
+    for (y, row) in grid.iter().enumerate() {
+        for (x, &c) in row.iter().enumerate() {
+            if c.is_ascii_digit() || c == '.' {
+                continue;
+            }
+            for (i, n) in numbers.iter().enumerate() {
+                if n.0.abs_diff(y) <= 1 && x + 1 >= n.1 && x <= n.2 {
+                    parts.insert(i);
+                }
+            }
+            let gears = adjacent(x, y).collect::<Vec<_>>();
+            if c == '*' && gears.len() == 2 {
+                ratios += gears[0] as u64 * gears[1] as u64;
+            }
+        }
+    }
+    (parts.iter().map(|&i| numbers[i].3).sum(), ratios)
+}
+
+fn day4_brute_force(cards: &[(Vec<u8>, Vec<u8>)]) -> (u32, usize) {
+    let matches = cards
+        .iter()
+        .map(|(winning, yours)| yours.iter().filter(|n| winning.contains(n)).count())
+        .collect::<Vec<_>>();
+    let points = matches
+        .iter()
+        .map(|&m| if m > 0 { 1 << (m - 1) } else { 0 })
+        .sum();
+    // process every single copy
+    let mut copies = (0..cards.len()).collect::<Vec<_>>();
+    let mut count = 0;
+    while let Some(card) = copies.pop() {
+        count += 1;
+        copies.extend(card + 1..=(card + matches[card]).min(cards.len() - 1));
+    }
+    (points, count)
+}
+
+fn day6_ways(time: u64, distance: u64) -> u64 {
+    (0..=time).filter(|t| t * (time - t) > distance).count() as u64
+}
+
+/// Hand type from high card (0) to five of a kind (6).
+fn day7_type(cards: &[char]) -> u8 {
+    let mut counts = cards
+        .iter()
+        .map(|c| cards.iter().filter(|d| *d == c).count())
+        .collect::<Vec<_>>();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    match counts[..] {
+        [5, ..] => 6,
+        [4, ..] => 5,
+        [3, 3, 3, 2, 2] => 4,
+        [3, ..] => 3,
+        [2, 2, 2, 2, 1] => 2,
+        [2, ..] => 1,
+        _ => 0,
+    }
+}
+
+fn day7_brute_force(hands: &[(String, u32)], joker: bool) -> u32 {
+    let order = if joker {
+        "J23456789TQKA"
+    } else {
+        "23456789TJQKA"
+    };
+    let mut ranked = hands
+        .iter()
+        .map(|(hand, bid)| {
+            let cards = hand.chars().collect::<Vec<_>>();
+            let hand_type = if joker {
+                // all jokers become the same card in the best hand
+                "23456789TQKA"
+                    .chars()
+                    .map(|r| {
+                        let replaced = cards.iter().map(|&c| if c == 'J' { r } else { c });
+                        day7_type(&replaced.collect::<Vec<_>>())
+                    })
+                    .max()
+                    .unwrap()
+            } else {
+                day7_type(&cards)
+            };
+            let values = cards.iter().map(|&c| order.find(c)).collect::<Vec<_>>();
+            (hand_type, values, *bid)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort();
+    ranked
+        .iter()
+        .enumerate()
+        .map(|(i, (_, _, bid))| (i as u32 + 1) * bid)
+        .sum()
+}
+
+fn polynomial(coefficients: &[i32], x: i32) -> i32 {
+    coefficients.iter().rev().fold(0, |acc, c| acc * x + c)
+}
+
+fn day11_brute_force(grid: &[Vec<char>]) -> usize {
+    // duplicate every empty row and column
+    let mut rows = vec![];
+    for row in grid {
+        rows.push(row.clone());
+        if !row.contains(&'#') {
+            rows.push(row.clone());
+        }
+    }
+    let mut expanded = vec![vec![]; rows.len()];
+    for x in 0..grid[0].len() {
+        let empty = grid.iter().all(|row| row[x] == '.');
+        for (y, row) in rows.iter().enumerate() {
+            expanded[y].push(row[x]);
+            if empty {
+                expanded[y].push(row[x]);
+            }
+        }
+    }
+    let galaxies = expanded
+        .iter()
+        .enumerate()
+        .flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, c)| **c == '#')
+                .map(move |(x, _)| (x, y))
+        })
+        .collect::<Vec<_>>();
+    let mut sum = 0;
+    for (i, a) in galaxies.iter().enumerate() {
+        for b in &galaxies[i + 1..] {
+            sum += a.0.abs_diff(b.0) + a.1.abs_diff(b.1);
+        }
+    }
+    sum
+}
+
+/// Relax all states until nothing changes, states are position, direction and run length.
+fn day17_brute_force(grid: &[Vec<u32>], min_run: usize, max_run: usize) -> Option<u32> {
+    let (w, h) = (grid[0].len() as i32, grid.len() as i32);
+    if (w, h) == (1, 1) {
+        // already at the end, which only counts without a minimum run
+        return (min_run == 1).then_some(0);
+    }
+    let dirs = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+    let mut best: HashMap<(i32, i32, usize, usize), u32> = HashMap::new();
+    for dir in [1, 2] {
+        let (x, y) = dirs[dir];
+        if x < w && y < h {
+            best.insert((x, y, dir, 1), grid[y as usize][x as usize]);
+        }
+    }
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for ((x, y, dir, run), cost) in best.clone() {
+            for (next, step) in dirs.iter().enumerate() {
+                if next == (dir + 2) % 4
+                    || (next == dir && run == max_run)
+                    || (next != dir && run < min_run)
+                {
+                    continue;
+                }
+                let (nx, ny) = (x + step.0, y + step.1);
+                if nx < 0 || ny < 0 || nx >= w || ny >= h {
+                    continue;
+                }
+                let key = (nx, ny, next, if next == dir { run + 1 } else { 1 });
+                let cost = cost + grid[ny as usize][nx as usize];
+                if best.get(&key).is_none_or(|&c| cost < c) {
+                    best.insert(key, cost);
+                    changed = true;
+                }
+            }
+        }
+    }
+    let ends = best
+        .iter()
+        .filter(|((x, y, _, run), _)| (*x, *y) == (w - 1, h - 1) && *run >= min_run);
+    ends.map(|(_, &c)| c).min()
+}
+
+/// Generated workflows as (checks, default), checks are (field, lower than, value,
+/// destination) and destinations are indices with `len` meaning A and `len + 1` meaning R.
+type Day19Workflows = [(Vec<(usize, bool, u32, usize)>, usize)];
+
+fn day19_accepts(workflows: &Day19Workflows, part: [u32; 4]) -> bool {
+    let mut current = 0;
+    while current < workflows.len() {
+        let (checks, default) = &workflows[current];
+        let matching = checks.iter().find(|&&(field, lower, value, _)| {
+            if lower {
+                part[field] < value
+            } else {
+                part[field] > value
+            }
+        });
+        current = matching.map_or(*default, |check| check.3);
+    }
+    current == workflows.len()
+}
+
+/// Every rating range between two condition values behaves the same, so one representative
+/// per range is enough.
+fn day19_brute_force(workflows: &Day19Workflows, parts: &[[u32; 4]]) -> (u32, u64) {
+    let accepted = parts.iter().filter(|&&part| day19_accepts(workflows, part));
+    let sum = accepted.map(|part| part.iter().sum::<u32>()).sum();
+    let bounds = (0..4)
+        .map(|field| {
+            let checks = workflows.iter().flat_map(|(checks, _)| checks);
+            let starts = checks
+                .filter(|check| check.0 == field)
+                .map(|&(_, lower, value, _)| if lower { value } else { value + 1 });
+            let mut starts = starts.chain([1, 4001]).collect::<Vec<_>>();
+            starts.sort_unstable();
+            starts.dedup();
+            starts
+        })
+        .collect::<Vec<_>>();
+    let mut combinations = 0;
+    for x in bounds[0].windows(2) {
+        for m in bounds[1].windows(2) {
+            for a in bounds[2].windows(2) {
+                for s in bounds[3].windows(2) {
+                    if day19_accepts(workflows, [x[0], m[0], a[0], s[0]]) {
+                        let size = [x, m, a, s].into_iter().map(|r| u64::from(r[1] - r[0]));
+                        combinations += size.product::<u64>();
+                    }
+                }
+            }
+        }
+    }
+    (sum, combinations)
+}
+
+/// Pulse counts after 1000 presses and the first press up to `max_presses` that sends a low
+/// pulse to rx.
+fn day20_brute_force(input: &str, max_presses: usize) -> (u64, Option<usize>) {
+    let mut modules = HashMap::new();
+    let mut memory = HashMap::<&str, HashMap<&str, bool>>::new();
+    for line in input.lines() {
+        let (module, outputs) = line.split_once(" -> ").unwrap();
+        let (kind, name) = match module.strip_prefix(['%', '&']) {
+            Some(name) => (&module[..1], name),
+            None => ("", module),
+        };
+        let outputs = outputs.split(", ").collect::<Vec<_>>();
+        for &output in &outputs {
+            memory.entry(output).or_default().insert(name, false);
+        }
+        modules.insert(name, (kind, outputs));
+    }
+    let mut on = HashSet::new();
+    let mut counts = [0u64; 2];
+    let mut rx_low = None;
+    for presses in 1..=max_presses.max(1000) {
+        let mut queue = std::collections::VecDeque::from([("button", "broadcaster", false)]);
+        while let Some((from, to, high)) = queue.pop_front() {
+            if presses <= 1000 {
+                counts[usize::from(high)] += 1;
+            }
+            if to == "rx" && !high {
+                rx_low = rx_low.or(Some(presses));
+            }
+            let Some((kind, outputs)) = modules.get(to) else {
+                continue;
+            };
+            let send = match *kind {
+                "%" if high => continue,
+                "%" if on.remove(to) => false,
+                "%" => on.insert(to),
+                "&" => {
+                    let inputs = memory.get_mut(to).unwrap();
+                    inputs.insert(from, high);
+                    !inputs.values().all(|&h| h)
+                }
+                _ => high,
+            };
+            queue.extend(outputs.iter().map(|&next| (to, next, send)));
+        }
+        if presses >= 1000 && rx_low.is_some() {
+            break;
+        }
+    }
+    (counts[0] * counts[1], rx_low)
+}
+
+/// Plots reachable in exactly `steps` steps on the finite map.
+fn day21_brute_force(grid: &[Vec<char>], start: (usize, usize), steps: usize) -> usize {
+    let mut current = HashSet::from([start]);
+    for _ in 0..steps {
+        let mut next = HashSet::new();
+        for &(x, y) in &current {
+            let neighbors = [
+                (x + 1, y),
+                (x.wrapping_sub(1), y),
+                (x, y + 1),
+                (x, y.wrapping_sub(1)),
+            ];
+            for (nx, ny) in neighbors {
+                if grid
+                    .get(ny)
+                    .and_then(|row| row.get(nx))
+                    .is_some_and(|&c| c != '#')
+                {
+                    next.insert((nx, ny));
+                }
+            }
+        }
+        current = next;
+    }
+    current.len()
+}
+
+/// Cells of a brick given by its two corners.
+fn day22_cells(brick: &([i64; 3], [i64; 3])) -> Vec<[i64; 3]> {
+    let (a, b) = brick;
+    let mut cells = vec![];
+    for x in a[0].min(b[0])..=a[0].max(b[0]) {
+        for y in a[1].min(b[1])..=a[1].max(b[1]) {
+            for z in a[2].min(b[2])..=a[2].max(b[2]) {
+                cells.push([x, y, z]);
+            }
+        }
+    }
+    cells
+}
+
+/// Drop bricks one step at a time until nothing moves, returns which bricks moved.
+fn day22_fall(bricks: &mut [Vec<[i64; 3]>]) -> HashSet<usize> {
+    let mut moved = HashSet::new();
+    let mut occupied = bricks.iter().flatten().copied().collect::<HashSet<_>>();
+    loop {
+        let mut any = false;
+        for (i, brick) in bricks.iter_mut().enumerate() {
+            let blocked = brick.iter().any(|&[x, y, z]| {
+                z == 1 || (occupied.contains(&[x, y, z - 1]) && !brick.contains(&[x, y, z - 1]))
+            });
+            if !blocked {
+                brick.iter().for_each(|cell| _ = occupied.remove(cell));
+                brick.iter_mut().for_each(|cell| cell[2] -= 1);
+                occupied.extend(brick.iter().copied());
+                moved.insert(i);
+                any = true;
+            }
+        }
+        if !any {
+            return moved;
+        }
+    }
+}
+
+fn day22_brute_force(bricks: &[([i64; 3], [i64; 3])]) -> (usize, usize) {
+    let mut settled = bricks.iter().map(day22_cells).collect::<Vec<_>>();
+    day22_fall(&mut settled);
+    let mut safe = 0;
+    let mut falls = 0;
+    for i in 0..settled.len() {
+        let mut rest = settled.clone();
+        rest.remove(i);
+        let moved = day22_fall(&mut rest).len();
+        safe += usize::from(moved == 0);
+        falls += moved;
+    }
+    (safe, falls)
+}
+
+/// Longest simple path from the top left to the bottom right opening, walking tile by tile.
+fn day23_brute_force(grid: &[Vec<char>], slopes: bool) -> Option<usize> {
+    fn walk(
+        grid: &[Vec<char>],
+        slopes: bool,
+        seen: &mut Vec<Vec<bool>>,
+        (x, y): (usize, usize),
+    ) -> Option<usize> {
+        if y == grid.len() - 1 {
+            return Some(0);
+        }
+        seen[y][x] = true;
+        let mut longest = None;
+        for (dx, dy, slope) in [(1, 0, '>'), (-1, 0, '<'), (0, 1, 'v'), (0, -1, '^')] {
+            if slopes && grid[y][x] != '.' && grid[y][x] != slope {
+                continue;
+            }
+            let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+            let Some(&tile) = grid.get(ny).and_then(|row| row.get(nx)) else {
+                continue;
+            };
+            if tile != '#' && !seen[ny][nx] {
+                longest = longest.max(walk(grid, slopes, seen, (nx, ny)).map(|l| l + 1));
+            }
+        }
+        seen[y][x] = false;
+        longest
+    }
+    let mut seen = vec![vec![false; grid[0].len()]; grid.len()];
+    walk(grid, slopes, &mut seen, (1, 0))
+}
+
+type Day24Hailstone = ([i64; 3], [i64; 3]);
+
+fn day24_line((p, v): &Day24Hailstone) -> String {
+    format!(
+        "{}, {}, {} @ {}, {}, {}",
+        p[0], p[1], p[2], v[0], v[1], v[2]
+    )
+}
+
+/// Future xy path crossings inside the real test area, approximated with floats. Fine for
+/// random hailstones, which practically never cross right on the border of the area.
+fn day24_float_crossings(hailstones: &[Day24Hailstone]) -> usize {
+    let (low, high) = (2e14, 4e14);
+    let mut count = 0;
+    for (i, (p, v)) in hailstones.iter().enumerate() {
+        for (q, w) in &hailstones[i + 1..] {
+            let [px, py, vx, vy, qx, qy, wx, wy] =
+                [p[0], p[1], v[0], v[1], q[0], q[1], w[0], w[1]].map(|c| c as f64);
+            let det = wx * vy - vx * wy;
+            if det == 0.0 {
+                continue;
+            }
+            let (dx, dy) = (qx - px, qy - py);
+            let t = (wx * dy - wy * dx) / det;
+            let s = (vx * dy - vy * dx) / det;
+            let (x, y) = (px + t * vx, py + t * vy);
+            if t >= 0.0 && s >= 0.0 && (low..=high).contains(&x) && (low..=high).contains(&y) {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn day1(lines in vec("[a-z1-9]{0,6}[1-9][a-z1-9]{0,6}", 1..20)) {
+        let input = join_lines(lines);
+        let (sum1, sum2) = day1_brute_force(&input);
+        prop_assert_eq!(answers(1, &input, Task::Both), (sum1.to_string(), sum2.to_string()));
+    }
+
+    #[test]
+    fn day2(games in vec(vec(vec((1..20u32, select(&["red", "green", "blue", "yellow"][..])), 1..4), 1..5), 1..15)) {
+        let input = join_lines(games.iter().enumerate().map(|(i, game)| {
+            let draws = game.iter().map(|draw| {
+                let cubes = draw.iter().map(|(n, color)| format!("{n} {color}"));
+                cubes.collect::<Vec<_>>().join(", ")
+            });
+            format!("Game {}: {}", i + 1, draws.collect::<Vec<_>>().join("; "))
+        }));
+        let (sum1, sum2) = day2_brute_force(&games);
+        prop_assert_eq!(answers(2, &input, Task::Both), (sum1.to_string(), sum2.to_string()));
+    }
+
+    #[test]
+    fn day3(mut grid in grid(3..12, prop_oneof![
+        6 => Just('.'),
+        3 => proptest::char::range('0', '9'),
+        1 => select(&['*', '#', '+', '$', '/'][..]),
+    ])) {
+        // numbers are at most three digits long
+        for row in grid.iter_mut() {
+            for x in 3..row.len() {
+                if row[x - 3..=x].iter().all(char::is_ascii_digit) {
+                    row[x] = '.';
+                }
+            }
+        }
+        let input = grid_string(&grid);
+        let (sum1, sum2) = day3_brute_force(&grid);
+        prop_assert_eq!(answers(3, &input, Task::Both), (sum1.to_string(), sum2.to_string()));
+    }
+
+    #[test]
+    fn day4(cards in vec((vec(1..30u8, 1..6), vec(1..30u8, 1..10)), 1..10)) {
+        let numbers = |numbers: &[u8]| {
+            numbers.iter().map(|n| format!("{n:>2}")).collect::<Vec<_>>().join(" ")
+        };
+        let input = join_lines(cards.iter().enumerate().map(|(i, (winning, yours))| {
+            format!("Card {:>3}: {} | {}", i + 1, numbers(winning), numbers(yours))
+        }));
+        let (points, count) = day4_brute_force(&cards);
+        prop_assert_eq!(answers(4, &input, Task::Both), (points.to_string(), count.to_string()));
+    }
+
+    #[test]
+    fn day5(
+        seeds in vec((0..50u64, 1..20u64), 1..4),
+        maps in vec(vec((0..80u64, 0..80u64, 1..20u64), 1..4), 7),
+    ) {
+        let names = [
+            "seed-to-soil", "soil-to-fertilizer", "fertilizer-to-water", "water-to-light",
+            "light-to-temperature", "temperature-to-humidity", "humidity-to-location",
+        ];
+        let seeds = seeds.into_iter().flat_map(|(start, len)| [start, len]).collect::<Vec<_>>();
+        let mut input = format!(
+            "seeds: {}\n",
+            seeds.iter().map(u64::to_string).collect::<Vec<_>>().join(" ")
+        );
+        for (name, ranges) in names.iter().zip(&maps) {
+            input += &format!("\n{name} map:\n");
+            input += &join_lines(ranges.iter().map(|(d, s, l)| format!("{d} {s} {l}")));
+        }
+        prop_assert_eq!(answers(5, &input, Task::Both), reference_answers(5, &input, Task::Both));
+    }
+
+    #[test]
+    fn day6(races in vec((1..100u64, 0..2500u64), 1..4)) {
+        let column = |n: u64| format!("{n:>5}");
+        let input = format!(
+            "Time:    {}\nDistance:{}\n",
+            races.iter().map(|r| column(r.0)).collect::<String>(),
+            races.iter().map(|r| column(r.1)).collect::<String>()
+        );
+        let ways = races.iter().map(|&(t, d)| day6_ways(t, d)).product::<u64>();
+        let concat = |n: &dyn Fn(&(u64, u64)) -> u64| {
+            races.iter().map(|r| n(r).to_string()).collect::<String>().parse::<u64>().unwrap()
+        };
+        let ways2 = day6_ways(concat(&|r| r.0), concat(&|r| r.1));
+        prop_assert_eq!(answers(6, &input, Task::Both), (ways.to_string(), ways2.to_string()));
+    }
+
+    #[test]
+    fn day7(hands in proptest::collection::hash_map("[2-9TJQKA]{5}", 1..1000u32, 1..30)) {
+        let hands = hands.into_iter().collect::<Vec<_>>();
+        let input = join_lines(hands.iter().map(|(hand, bid)| format!("{hand} {bid}")));
+        let expected = (day7_brute_force(&hands, false), day7_brute_force(&hands, true));
+        prop_assert_eq!(answers(7, &input, Task::Both), (expected.0.to_string(), expected.1.to_string()));
+    }
+
+    #[test]
+    fn day8(
+        directions in "[LR]{1,5}",
+        (suffixes, links) in (0..8usize).prop_flat_map(|extra| (
+            vec(select(&['A', 'X', 'Z'][..]), extra),
+            vec((0..extra + 2, 0..extra + 2), extra + 2),
+        )),
+    ) {
+        let mut names = vec![String::from("AAA"), String::from("ZZZ")];
+        names.extend(suffixes.iter().enumerate().map(|(i, s)| format!("{i:02}{s}")));
+        let nodes = names
+            .iter()
+            .zip(&links)
+            .map(|(name, &(l, r))| (name.clone(), (names[l].clone(), names[r].clone())))
+            .collect::<HashMap<_, _>>();
+        let input = format!("{directions}\n\n") + &join_lines(
+            nodes.iter().map(|(name, (l, r))| format!("{name} = ({l}, {r})")),
+        );
+        // part 2 assumes that every ghost loops with the same period, which random maps don't
+        prop_assert_eq!(answers(8, &input, Task::One), reference_answers(8, &input, Task::One));
+    }
+
+    #[test]
+    fn day9(rows in vec((vec(-5..5i32, 1..4), 5..10i32), 1..10)) {
+        let input = join_lines(rows.iter().map(|(coefficients, len)| {
+            let values = (0..*len).map(|x| polynomial(coefficients, x).to_string());
+            values.collect::<Vec<_>>().join(" ")
+        }));
+        // the sequences are polynomials, so extrapolating them has to give the exact values
+        let next = rows.iter().map(|(c, len)| polynomial(c, *len)).sum::<i32>();
+        let previous = rows.iter().map(|(c, _)| polynomial(c, -1)).sum::<i32>();
+        prop_assert_eq!(answers(9, &input, Task::Both), (next.to_string(), previous.to_string()));
+    }
+
+    #[test]
+    fn day10(
+        (w, h, x0, y0, x1, y1) in (3..10usize, 3..10usize).prop_flat_map(|(w, h)| {
+            (Just(w), Just(h), 0..w - 1, 0..h - 1)
+                .prop_flat_map(|(w, h, x0, y0)| (Just(w), Just(h), Just(x0), Just(y0), x0 + 1..w, y0 + 1..h))
+        }),
+        start in any::<proptest::sample::Index>(),
+    ) {
+        // rectangular loop, the start replaces any of its tiles
+        let mut grid = vec![vec!['.'; w]; h];
+        let horizontal = (x0..=x1).flat_map(|x| [(x, y0, '-'), (x, y1, '-')]);
+        let vertical = (y0 + 1..y1).flat_map(|y| [(x0, y, '|'), (x1, y, '|')]);
+        let tiles = horizontal.chain(vertical).collect::<Vec<_>>();
+        for &(x, y, pipe) in &tiles {
+            grid[y][x] = pipe;
+        }
+        grid[y0][x0] = 'F';
+        grid[y0][x1] = '7';
+        grid[y1][x0] = 'L';
+        grid[y1][x1] = 'J';
+        let (sx, sy, _) = tiles[start.index(tiles.len())];
+        grid[sy][sx] = 'S';
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let expected = ((dx + dy).to_string(), ((dx - 1) * (dy - 1)).to_string());
+        prop_assert_eq!(answers(10, &grid_string(&grid), Task::Both), expected);
+    }
+
+    #[test]
+    fn day11(grid in grid(1..10, prop_oneof![4 => Just('.'), 1 => Just('#')])) {
+        let expected = day11_brute_force(&grid).to_string();
+        prop_assert_eq!(answers(11, &grid_string(&grid), Task::Both).0, expected);
+    }
+
+    #[test]
+    fn day12(rows in vec(("[.#?]{1,12}", vec(1..4usize, 1..4)), 1..6)) {
+        let input = join_lines(rows.iter().map(|(line, groups)| {
+            let groups = groups.iter().map(usize::to_string).collect::<Vec<_>>();
+            format!("{line} {}", groups.join(","))
+        }));
+        prop_assert_eq!(answers(12, &input, Task::Both), reference_answers(12, &input, Task::Both));
+    }
+
+    #[test]
+    fn day13(patterns in vec(grid(1..10, select(&['.', '#'][..])), 1..4)) {
+        let input = patterns.iter().map(|p| grid_string(p)).collect::<Vec<_>>().join("\n");
+        answers(13, &input, Task::Both);
+    }
+
+    #[test]
+    fn day14(grid in grid(1..8, prop_oneof![3 => Just('.'), 2 => Just('O'), 1 => Just('#')])) {
+        let input = grid_string(&grid);
+        prop_assert_eq!(answers(14, &input, Task::Both), reference_answers(14, &input, Task::Both));
+    }
+
+    #[test]
+    fn day15(steps in vec("[a-z]{1,4}(-|=[1-9])", 1..30)) {
+        answers(15, &steps.join(","), Task::Both);
+    }
+
+    #[test]
+    fn day16(grid in grid(1..12, prop_oneof![
+        6 => Just('.'),
+        1 => select(&['/', '\\', '|', '-'][..]),
+    ])) {
+        answers(16, &grid_string(&grid), Task::Both);
+    }
+
+    #[test]
+    fn day17(grid in grid(1..7, 1..10u32)) {
+        let input = join_lines(grid.iter().map(|row| row.iter().map(u32::to_string).collect()));
+        let expected = |min, max| {
+            day17_brute_force(&grid, min, max).map_or(String::from("unreachable"), |c| c.to_string())
+        };
+        prop_assert_eq!(answers(17, &input, Task::Both), (expected(1, 3), expected(4, 10)));
+    }
+
+    #[test]
+    fn day18(columns in vec((1..5i64, 1..5i64), 1..6)) {
+        // histogram shaped loop: along the bottom to the right and back over the columns
+        let width = columns.iter().map(|c| c.0).sum::<i64>();
+        let mut plan = vec![('R', width), ('U', columns[columns.len() - 1].1)];
+        for (i, &(w, height)) in columns.iter().enumerate().rev() {
+            plan.push(('L', w));
+            let next = if i > 0 { columns[i - 1].1 } else { 0 };
+            match height.cmp(&next) {
+                std::cmp::Ordering::Less => plan.push(('U', next - height)),
+                std::cmp::Ordering::Greater => plan.push(('D', height - next)),
+                std::cmp::Ordering::Equal => {}
+            }
+        }
+        // the color encodes the same plan, so both parts have the same answer
+        let input = join_lines(plan.iter().map(|&(dir, dist)| {
+            let code = "RDLU".find(dir).unwrap();
+            format!("{dir} {dist} (#{dist:05x}{code})")
+        }));
+        let expected = reference_answers(18, &input, Task::Both);
+        prop_assert_eq!(&expected.0, &expected.1);
+        prop_assert_eq!(answers(18, &input, Task::Both), expected);
+    }
+
+    #[test]
+    fn day19(
+        workflows in vec(vec((0..4usize, any::<bool>(), 1..4000u32, any::<proptest::sample::Index>()), 0..4), 1..8),
+        parts in vec([1..4000u32, 1..4000, 1..4000, 1..4000], 1..10),
+    ) {
+        // workflows only send parts to later workflows, so there are no cycles
+        let count = workflows.len();
+        let workflows = workflows.iter().enumerate().map(|(i, checks)| {
+            let checks = checks.iter().map(|&(field, lower, value, ref index)| {
+                (field, lower, value, i + 1 + index.index(count + 1 - i))
+            });
+            (checks.collect::<Vec<_>>(), count + 1 - i % 2)
+        }).collect::<Vec<_>>();
+        let name = |i: usize| match i {
+            0 => String::from("in"),
+            i if i == count => String::from("A"),
+            i if i == count + 1 => String::from("R"),
+            i => format!("w{i}"),
+        };
+        let mut input = join_lines(workflows.iter().enumerate().map(|(i, (checks, default))| {
+            let checks = checks.iter().map(|&(field, lower, value, dest)| {
+                let field = ['x', 'm', 'a', 's'][field];
+                format!("{field}{}{value}:{},", if lower { '<' } else { '>' }, name(dest))
+            });
+            format!("{}{{{}{}}}", name(i), checks.collect::<String>(), name(*default))
+        }));
+        input += "\n";
+        input += &join_lines(parts.iter().map(|[x, m, a, s]| format!("{{x={x},m={m},a={a},s={s}}}")));
+        let (sum, combinations) = day19_brute_force(&workflows, &parts);
+        prop_assert_eq!(
+            answers(19, &input, Task::Both),
+            (sum.to_string(), combinations.to_string())
+        );
+    }
+
+    #[test]
+    fn day20(counters in 1..4usize, seed in any::<u64>()) {
+        let input = generate(20, counters, seed);
+        // a single counter sends a low pulse to rx after at most 4095 presses, several counters
+        // only after the least common multiple of their values, which is too far to simulate
+        if counters == 1 {
+            let (product, presses) = day20_brute_force(&input, 4095);
+            let presses = presses.map_or(String::from("unreachable"), |p| p.to_string());
+            prop_assert_eq!(answers(20, &input, Task::Both), (product.to_string(), presses));
+        } else {
+            let (product, _) = day20_brute_force(&input, 1000);
+            prop_assert_eq!(answers(20, &input, Task::One).0, product.to_string());
+        }
+    }
+
+    #[test]
+    fn day21(
+        mut grid in grid(1..12, prop_oneof![4 => Just('.'), 1 => Just('#')]),
+        start in any::<(proptest::sample::Index, proptest::sample::Index)>(),
+    ) {
+        let start = (start.0.index(grid[0].len()), start.1.index(grid.len()));
+        grid[start.1][start.0] = 'S';
+        let input = grid_string(&grid);
+        let expected = day21_brute_force(&grid, start, 64).to_string();
+        // part 2 walks too far for a brute force
+        prop_assert_eq!(answers(21, &input, Task::Both).0, expected);
+    }
+
+    #[test]
+    fn day22(bricks in vec((0..4i64, 0..4i64, 0..3usize, 0..3i64, 1..3i64), 1..10)) {
+        // stacked with gaps so that no bricks overlap before falling
+        let mut z = 1;
+        let bricks = bricks.iter().map(|&(x, y, axis, length, gap)| {
+            let mut end = [x, y, z];
+            end[axis] += length;
+            let brick = ([x, y, z], end);
+            z = end[2] + gap;
+            brick
+        }).collect::<Vec<_>>();
+        let input = join_lines(bricks.iter().map(|(a, b)| {
+            format!("{},{},{}~{},{},{}", a[0], a[1], a[2], b[0], b[1], b[2])
+        }));
+        let (safe, falls) = day22_brute_force(&bricks);
+        prop_assert_eq!(answers(22, &input, Task::Both), (safe.to_string(), falls.to_string()));
+    }
+
+    #[test]
+    fn day23(inner in grid(1..6, prop_oneof![4 => Just('.'), 2 => Just('#'), 1 => Just('>'), 1 => Just('v')])) {
+        // walls around the tiles with the start and the end in the corners
+        let width = inner[0].len() + 2;
+        let mut grid = vec![vec!['#'; width]];
+        grid.extend(inner.iter().map(|row| [vec!['#'], row.clone(), vec!['#']].concat()));
+        grid.push(vec!['#'; width]);
+        grid[0][1] = '.';
+        grid.last_mut().unwrap()[width - 2] = '.';
+        let input = grid_string(&grid);
+        let expected = |slopes| day23_brute_force(&grid, slopes).map_or(String::from("unreachable"), |l| l.to_string());
+        prop_assert_eq!(answers(23, &input, Task::Both), (expected(true), expected(false)));
+    }
+
+    #[test]
+    fn day24_crossings(hailstones in vec((uniform3(100_000_000_000_000..500_000_000_000_000i64), uniform3(-500..500i64)), 2..20)) {
+        let input = join_lines(hailstones.iter().map(day24_line));
+        prop_assert_eq!(answers(24, &input, Task::One).0, day24_float_crossings(&hailstones).to_string());
+    }
+
+    #[test]
+    fn day24_throw(
+        position in uniform3(-1_000_000..1_000_000i64),
+        velocity in uniform3(-20..20i64),
+        hits in vec((1..1000i64, uniform3(-20..20i64)), 4..10),
+    ) {
+        // four hailstones hit at different times from different directions pin down a single
+        // throw, with only three the linear system of the solver can be underdetermined
+        let relative = |v: [i64; 3]| [0, 1, 2].map(|i| velocity[i] - v[i]);
+        let independent = hits.iter().enumerate().all(|(i, &(time, v))| {
+            hits[..i].iter().all(|&(other_time, w)| {
+                let (a, b) = (relative(v), relative(w));
+                let cross = [a[1] * b[2] - a[2] * b[1], a[2] * b[0] - a[0] * b[2], a[0] * b[1] - a[1] * b[0]];
+                time != other_time && cross != [0; 3]
+            })
+        });
+        prop_assume!(independent);
+        // every hailstone starts where the rock hits it after some time
+        let hailstones = hits.iter().map(|&(time, v)| {
+            ([0, 1, 2].map(|i| position[i] + time * relative(v)[i]), v)
+        }).collect::<Vec<_>>();
+        let input = join_lines(hailstones.iter().map(day24_line));
+        prop_assert_eq!(answers(24, &input, Task::Two).1, position.iter().sum::<i64>().to_string());
+    }
+
+    #[test]
+    fn day25(
+        sizes in (5..12usize, 5..12usize),
+        extra in vec((0..24usize, 0..24usize), 0..10),
+        bridges in uniform3((0..12usize, 0..12usize)),
+        order in Just((0..24).collect::<Vec<usize>>()).prop_shuffle(),
+    ) {
+        // two groups in which every component is wired to the next two, so only the three
+        // bridges between the groups can be cut
+        let (a, b) = sizes;
+        let mut wires = vec![];
+        for (start, len) in [(0, a), (a, b)] {
+            for i in 0..len {
+                wires.push((start + i, start + (i + 1) % len));
+                wires.push((start + i, start + (i + 2) % len));
+            }
+        }
+        for &(x, y) in &extra {
+            let (x, y) = (x % (a + b), y % (a + b));
+            // only within a group
+            if (x < a) == (y < a) {
+                wires.push((x, y));
+            }
+        }
+        let bridges = bridges.map(|(x, y)| (x % a, a + y % b));
+        prop_assume!(bridges[0] != bridges[1] && bridges[0] != bridges[2] && bridges[1] != bridges[2]);
+        wires.extend(bridges);
+        let name = |i: usize| format!("c{}", char::from(b'a' + order[i] as u8));
+        let input = join_lines(wires.iter().map(|&(x, y)| format!("{}: {}", name(x), name(y))));
+        prop_assert_eq!(answers(25, &input, Task::One).0, (a * b).to_string());
+    }
+}
@@ -0,0 +1,334 @@
+//! Slow but obviously correct solvers for days with shortcuts or subtle optimizations, used to
+//! cross-check the optimized solvers on small inputs. Only built with the `reference` feature.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{parse::ParseError, util::Normalize, Task};
+
+/// Days that have a reference solver.
+pub const DAYS: [usize; 5] = [5, 8, 12, 14, 18];
+
+/// Rough upper bound for the steps a reference takes before it gives up on an input.
+const MAX_WORK: usize = 10_000_000;
+
+/// Outcome of comparing one part of the optimized solver with the reference.
+#[derive(Debug, PartialEq)]
+pub enum Check {
+    Agree(String),
+    Disagree {
+        optimized: String,
+        reference: String,
+    },
+    /// the input is too large for the reference
+    Skipped(String),
+}
+
+/// Solve a day with the optimized and the reference solver and compare both parts.
+pub fn cross_check(day: usize, input: &str) -> Result<[Check; 2], ParseError> {
+    let (res1, res2, _) = crate::try_solve(day, input, Task::Both)?;
+    let (ref1, ref2) = solve(day, input, Task::Both);
+    let check = |optimized: String, reference: Option<String>| match reference {
+        None => Check::Skipped(optimized),
+        Some(reference) if reference == optimized => Check::Agree(optimized),
+        Some(reference) => Check::Disagree {
+            optimized,
+            reference,
+        },
+    };
+    Ok([check(res1, ref1), check(res2, ref2)])
+}
+
+/// Answers of the parts in the task, `None` for the other part and if the input is too large
+/// to brute force that part. The input has to be valid, see [`crate::try_solve`].
+pub fn solve(day: usize, input: &str, task: Task) -> (Option<String>, Option<String>) {
+    let input = Normalize::DEFAULT.apply(input);
+    match day {
+        5 => day05(&input, task),
+        8 => day08(&input, task),
+        12 => day12(&input, task),
+        14 => day14(&input, task),
+        18 => day18(&input, task),
+        _ => panic!("no reference solver for day {day}"),
+    }
+}
+
+fn wanted(task: Task, part: usize) -> bool {
+    !matches!((task, part), (Task::One, 2) | (Task::Two, 1))
+}
+
+fn numbers(s: &str) -> Vec<u64> {
+    s.split_whitespace().map(|n| n.parse().unwrap()).collect()
+}
+
+/// Map every single seed through all maps.
+fn day05(input: &str, task: Task) -> (Option<String>, Option<String>) {
+    let mut blocks = input.split("\n\n");
+    let seeds = numbers(blocks.next().unwrap().trim_start_matches("seeds:"));
+    let maps = blocks
+        .map(|block| block.lines().skip(1).map(numbers).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    // the first range containing the value maps it, otherwise it stays the same
+    let location = |seed: u64| {
+        maps.iter().fold(seed, |value, ranges| {
+            ranges
+                .iter()
+                .find(|r| (r[1]..r[1].saturating_add(r[2])).contains(&value))
+                .map_or(value, |r| r[0] + (value - r[1]))
+        })
+    };
+    let lowest = |locations: &mut dyn Iterator<Item = u64>| {
+        locations
+            .min()
+            .map_or(String::from("no solution"), |l| l.to_string())
+    };
+    let part1 = wanted(task, 1).then(|| lowest(&mut seeds.iter().map(|&s| location(s))));
+    let pairs = seeds.chunks_exact(2);
+    let count = pairs.clone().map(|p| p[1]).fold(0, u64::saturating_add);
+    let part2 = (wanted(task, 2) && count <= MAX_WORK as u64).then(|| {
+        let seeds = pairs.flat_map(|p| p[0]..p[0].saturating_add(p[1]));
+        lowest(&mut seeds.map(location))
+    });
+    (part1, part2)
+}
+
+/// Walk all ghosts at the same time instead of combining their loops.
+fn day08(input: &str, task: Task) -> (Option<String>, Option<String>) {
+    let (directions, nodes) = input.split_once("\n\n").unwrap();
+    let directions = directions
+        .bytes()
+        .map(|d| usize::from(d == b'R'))
+        .collect::<Vec<_>>();
+    let nodes = nodes
+        .lines()
+        .map(|line| {
+            let (name, next) = line.split_once(" = ").unwrap();
+            let (left, right) = next.trim_matches(['(', ')']).split_once(", ").unwrap();
+            (name, [left, right])
+        })
+        .collect::<HashMap<_, _>>();
+    let start = nodes.keys().copied().filter(|&n| n == "AAA").collect();
+    let ghosts = nodes.keys().copied().filter(|n| n.ends_with('A')).collect();
+    (
+        wanted(task, 1)
+            .then(|| walk(&nodes, &directions, start, |n| n == "ZZZ"))
+            .flatten(),
+        wanted(task, 2)
+            .then(|| walk(&nodes, &directions, ghosts, |n| n.ends_with('Z')))
+            .flatten(),
+    )
+}
+
+fn walk<'a>(
+    nodes: &HashMap<&'a str, [&'a str; 2]>,
+    directions: &[usize],
+    mut ghosts: Vec<&'a str>,
+    end: fn(&str) -> bool,
+) -> Option<String> {
+    if ghosts.is_empty() {
+        return Some(String::from("unreachable"));
+    }
+    let states = u32::try_from(ghosts.len())
+        .ok()
+        .and_then(|n| nodes.len().checked_pow(n))
+        .and_then(|n| n.checked_mul(directions.len()));
+    for step in 0..MAX_WORK {
+        if ghosts.iter().all(|g| end(g)) {
+            return Some(step.to_string());
+        }
+        // after more steps than there are states one repeated, so the walk loops forever
+        if states.is_some_and(|states| step >= states) {
+            return Some(String::from("unreachable"));
+        }
+        let dir = directions[step % directions.len()];
+        for ghost in &mut ghosts {
+            *ghost = nodes[ghost][dir];
+        }
+    }
+    None
+}
+
+/// Try every assignment of the unknown springs for part 1, part 2 counts the arrangements
+/// with a table over line position and group instead.
+fn day12(input: &str, task: Task) -> (Option<String>, Option<String>) {
+    let rows = input
+        .lines()
+        .map(|line| {
+            let (springs, groups) = line.split_once(' ').unwrap();
+            let groups = groups.split(',').map(|g| g.parse().unwrap());
+            (springs.as_bytes(), groups.collect::<Vec<usize>>())
+        })
+        .collect::<Vec<_>>();
+    let assignments = rows.iter().map(|(springs, _)| {
+        let unknown = springs.iter().filter(|&&c| c == b'?').count();
+        1usize.checked_shl(unknown as u32).unwrap_or(usize::MAX)
+    });
+    let part1 =
+        (wanted(task, 1) && assignments.fold(0, usize::saturating_add) <= MAX_WORK).then(|| {
+            let counts = rows.iter().map(|(springs, groups)| {
+                let unknown = (0..springs.len()).filter(|&i| springs[i] == b'?');
+                let unknown = unknown.collect::<Vec<_>>();
+                (0..1usize << unknown.len())
+                    .filter(|mask| {
+                        let mut springs = springs.to_vec();
+                        for (bit, &i) in unknown.iter().enumerate() {
+                            springs[i] = if mask >> bit & 1 == 1 { b'#' } else { b'.' };
+                        }
+                        let found = springs.split(|&c| c == b'.').filter(|g| !g.is_empty());
+                        found.map(<[u8]>::len).eq(groups.iter().copied())
+                    })
+                    .count()
+            });
+            counts.sum::<usize>().to_string()
+        });
+    let part2 = wanted(task, 2).then(|| {
+        let unfolded = rows.iter().map(|(springs, groups)| {
+            let springs = [*springs; 5].join(&b'?');
+            arrangements(&springs, &groups.repeat(5))
+        });
+        unfolded.sum::<usize>().to_string()
+    });
+    (part1, part2)
+}
+
+fn arrangements(springs: &[u8], groups: &[usize]) -> usize {
+    let (n, m) = (springs.len(), groups.len());
+    // ways[i][g]: arrangements of springs[i..] with groups[g..], a group ending at the last
+    // spring skips the separator and continues at n + 1
+    let mut ways = vec![vec![0; m + 1]; n + 2];
+    ways[n][m] = 1;
+    ways[n + 1][m] = 1;
+    for i in (0..n).rev() {
+        for g in 0..=m {
+            if springs[i] != b'#' {
+                ways[i][g] += ways[i + 1][g];
+            }
+            if g < m && springs[i] != b'.' {
+                let end = i + groups[g];
+                if end <= n && !springs[i..end].contains(&b'.') && springs.get(end) != Some(&b'#') {
+                    ways[i][g] += ways[end + 1][g + 1];
+                }
+            }
+        }
+    }
+    ways[0][0]
+}
+
+/// Roll rocks one tile at a time and keep every state to find the loop.
+fn day14(input: &str, task: Task) -> (Option<String>, Option<String>) {
+    let grid = input
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut north = grid.clone();
+    roll_north(&mut north);
+    let cells = grid.len() * grid[0].len();
+    let part2 = (wanted(task, 2) && cells * cells <= MAX_WORK).then(|| {
+        let mut history = vec![grid];
+        loop {
+            let mut next = history[history.len() - 1].clone();
+            // north, west, south and east, the west side is north after rotating clockwise
+            for _ in 0..4 {
+                roll_north(&mut next);
+                next = rotate(&next);
+            }
+            if let Some(start) = history.iter().position(|g| *g == next) {
+                let index = start + (1_000_000_000 - start) % (history.len() - start);
+                return load(&history[index]).to_string();
+            }
+            history.push(next);
+        }
+    });
+    (wanted(task, 1).then(|| load(&north).to_string()), part2)
+}
+
+fn roll_north(grid: &mut [Vec<char>]) {
+    let mut moved = true;
+    while moved {
+        moved = false;
+        for y in 1..grid.len() {
+            for x in 0..grid[y].len() {
+                if grid[y][x] == 'O' && grid[y - 1][x] == '.' {
+                    grid[y][x] = '.';
+                    grid[y - 1][x] = 'O';
+                    moved = true;
+                }
+            }
+        }
+    }
+}
+
+/// Rotate clockwise.
+fn rotate(grid: &[Vec<char>]) -> Vec<Vec<char>> {
+    (0..grid[0].len())
+        .map(|x| grid.iter().rev().map(|row| row[x]).collect())
+        .collect()
+}
+
+fn load(grid: &[Vec<char>]) -> usize {
+    let rows = grid.iter().rev().enumerate();
+    rows.map(|(i, row)| (i + 1) * row.iter().filter(|&&c| c == 'O').count())
+        .sum()
+}
+
+/// Dig every tile of the trench and flood the outside.
+fn day18(input: &str, task: Task) -> (Option<String>, Option<String>) {
+    let plans = input.lines().map(|line| {
+        let mut fields = line.split(' ');
+        let dir = fields.next().unwrap();
+        let dist = fields.next().unwrap().parse().unwrap();
+        let color = fields.next().unwrap().trim_matches(['(', '#', ')']);
+        let color_dist = i64::from_str_radix(&color[..5], 16).unwrap();
+        let color_dir = ["R", "D", "L", "U"][usize::from(color.as_bytes()[5] - b'0')];
+        ((dir, dist), (color_dir, color_dist))
+    });
+    let (plan1, plan2) = plans.unzip::<_, _, Vec<_>, Vec<_>>();
+    (
+        wanted(task, 1).then(|| dig(&plan1)).flatten(),
+        wanted(task, 2).then(|| dig(&plan2)).flatten(),
+    )
+}
+
+fn dig(plan: &[(&str, i64)]) -> Option<String> {
+    let steps = plan.iter().map(|&(dir, dist)| {
+        let step = match dir {
+            "U" => (0, -1),
+            "D" => (0, 1),
+            "L" => (-1, 0),
+            _ => (1, 0),
+        };
+        (step, dist)
+    });
+    let mut corners = vec![(0, 0)];
+    for ((dx, dy), dist) in steps.clone() {
+        let (x, y) = corners[corners.len() - 1];
+        corners.push((x + dx * dist, y + dy * dist));
+    }
+    // bounding box with a border of one tile, so the outside is connected
+    let min_x = corners.iter().map(|c| c.0).min().unwrap() - 1;
+    let max_x = corners.iter().map(|c| c.0).max().unwrap() + 1;
+    let min_y = corners.iter().map(|c| c.1).min().unwrap() - 1;
+    let max_y = corners.iter().map(|c| c.1).max().unwrap() + 1;
+    let area = (max_x - min_x + 1) * (max_y - min_y + 1);
+    if area > MAX_WORK as i64 {
+        return None;
+    }
+    let mut trench = HashSet::from([(0, 0)]);
+    let mut pos = (0, 0);
+    for ((dx, dy), dist) in steps {
+        for _ in 0..dist {
+            pos = (pos.0 + dx, pos.1 + dy);
+            trench.insert(pos);
+        }
+    }
+    let mut outside = HashSet::from([(min_x, min_y)]);
+    let mut todo = vec![(min_x, min_y)];
+    while let Some((x, y)) = todo.pop() {
+        for next in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            let in_box = (min_x..=max_x).contains(&next.0) && (min_y..=max_y).contains(&next.1);
+            if in_box && !trench.contains(&next) && outside.insert(next) {
+                todo.push(next);
+            }
+        }
+    }
+    Some((area - outside.len() as i64).to_string())
+}
@@ -0,0 +1,282 @@
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::OnceLock,
+};
+
+use strum::{Display, EnumString};
+
+pub type Color = [u8; 3];
+
+pub const WHITE: Color = [230, 230, 230];
+pub const GRAY: Color = [110, 110, 110];
+pub const DARK: Color = [40, 40, 40];
+pub const RED: Color = [220, 50, 47];
+pub const GREEN: Color = [80, 200, 80];
+pub const YELLOW: Color = [240, 200, 40];
+pub const BLUE: Color = [60, 130, 230];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Color,
+}
+
+/// A rectangular grid of colored characters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    pub fn new(width: usize, height: usize) -> Self {
+        let empty = Cell {
+            symbol: ' ',
+            color: DARK,
+        };
+        Self {
+            width,
+            height,
+            cells: vec![empty; width * height],
+        }
+    }
+
+    /// Create a frame with one cell per grid entry.
+    pub fn from_grid<T>(grid: &[Vec<T>], cell: impl Fn(&T) -> (char, Color)) -> Self {
+        let mut frame = Self::new(grid.first().map_or(0, Vec::len), grid.len());
+        for (y, row) in grid.iter().enumerate() {
+            for (x, value) in row.iter().enumerate() {
+                let (symbol, color) = cell(value);
+                frame.set(x, y, symbol, color);
+            }
+        }
+        frame
+    }
+
+    /// Set a cell, positions outside of the frame are ignored.
+    pub fn set(&mut self, x: usize, y: usize, symbol: char, color: Color) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = Cell { symbol, color };
+        }
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// RGB bytes of every row of pixels, scaled up by [`SCALE`].
+    fn pixel_rows(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        self.rows().flat_map(|row| {
+            let line = row.iter().flat_map(|c| [c.color; SCALE]).flatten();
+            vec![line.collect::<Vec<_>>(); SCALE]
+        })
+    }
+}
+
+/// Implemented by solver state that can be drawn as a frame.
+pub trait Render {
+    fn render(&self) -> Frame;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumString, Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Backend {
+    /// plain characters
+    Ascii,
+    /// characters with ANSI true color escape codes
+    Ansi,
+    /// binary PPM images, every cell is a square of pixels
+    Ppm,
+    /// uncompressed PNG images with the same pixels as PPM
+    Png,
+}
+
+/// Pixels per cell side in the image backends.
+const SCALE: usize = 4;
+
+impl Backend {
+    fn extension(&self) -> &'static str {
+        match self {
+            Self::Ascii => "txt",
+            Self::Ansi => "ansi",
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+        }
+    }
+
+    pub fn write(&self, frame: &Frame, out: &mut impl Write) -> std::io::Result<()> {
+        match self {
+            Self::Ascii => {
+                for row in frame.rows() {
+                    let line = row.iter().map(|c| c.symbol).collect::<String>();
+                    writeln!(out, "{line}")?;
+                }
+            }
+            Self::Ansi => {
+                for row in frame.rows() {
+                    for Cell { symbol, color } in row {
+                        let [r, g, b] = color;
+                        write!(out, "\x1b[38;2;{r};{g};{b}m{symbol}")?;
+                    }
+                    writeln!(out, "\x1b[0m")?;
+                }
+            }
+            Self::Ppm => {
+                write!(
+                    out,
+                    "P6\n{} {}\n255\n",
+                    frame.width * SCALE,
+                    frame.height * SCALE
+                )?;
+                for line in frame.pixel_rows() {
+                    out.write_all(&line)?;
+                }
+            }
+            Self::Png => {
+                out.write_all(b"\x89PNG\r\n\x1a\n")?;
+                let mut header = vec![];
+                header.extend(((frame.width * SCALE) as u32).to_be_bytes());
+                header.extend(((frame.height * SCALE) as u32).to_be_bytes());
+                // 8 bit RGB, default compression, filter and no interlacing
+                header.extend([8, 2, 0, 0, 0]);
+                png_chunk(out, b"IHDR", &header)?;
+                // every scanline starts with filter type 0
+                let pixels = frame.pixel_rows().flat_map(|line| [vec![0], line].concat());
+                png_chunk(out, b"IDAT", &zlib_stored(&pixels.collect::<Vec<_>>()))?;
+                png_chunk(out, b"IEND", &[])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn png_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> std::io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    out.write_all(&crc32(kind.iter().chain(data)).to_be_bytes())
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+/// A zlib stream of uncompressed deflate blocks, which keeps PNG free of a compression library.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        out.push(u8::from(blocks.peek().is_none()));
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend(((b << 16) | a).to_be_bytes());
+    out
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderConfig {
+    pub dir: PathBuf,
+    pub backend: Backend,
+    /// emit every nth step, only the final state is rendered if None
+    pub every: Option<usize>,
+}
+
+static CONFIG: OnceLock<RenderConfig> = OnceLock::new();
+
+/// Enable rendering for the rest of the program.
+pub fn init(config: RenderConfig) -> std::io::Result<()> {
+    fs::create_dir_all(&config.dir)?;
+    CONFIG
+        .set(config)
+        .expect("rendering is already initialized");
+    Ok(())
+}
+
+pub fn enabled() -> bool {
+    CONFIG.get().is_some()
+}
+
+/// Emit an intermediate frame, it is only rendered if step is a multiple of the configured interval.
+pub fn step(name: &str, step: usize, state: &impl Render) {
+    let Some(config) = CONFIG.get() else {
+        return;
+    };
+    match config.every {
+        Some(every) if step.is_multiple_of(every.max(1)) => {
+            write_frame(config, &format!("{name}_{step:06}"), state)
+        }
+        _ => {}
+    }
+}
+
+/// Emit the final state.
+pub fn finish(name: &str, state: &impl Render) {
+    if let Some(config) = CONFIG.get() {
+        write_frame(config, &format!("{name}_final"), state);
+    }
+}
+
+fn write_frame(config: &RenderConfig, file_name: &str, state: &impl Render) {
+    let path = config
+        .dir
+        .join(file_name)
+        .with_extension(config.backend.extension());
+    let file = File::create(&path)
+        .unwrap_or_else(|e| panic!("could not create '{}': {e}", path.display()));
+    let mut out = BufWriter::new(file);
+    config
+        .backend
+        .write(&state.render(), &mut out)
+        .and_then(|_| out.flush())
+        .unwrap_or_else(|e| panic!("could not write '{}': {e}", path.display()));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn backends() {
+        let mut frame = Frame::from_grid(&[vec![1, 2], vec![3, 4]], |n| {
+            (char::from_digit(*n, 10).unwrap(), WHITE)
+        });
+        frame.set(1, 1, '#', RED);
+        frame.set(5, 5, '#', RED);
+        let mut out = vec![];
+        Backend::Ascii.write(&frame, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "12\n3#\n");
+        let mut out = vec![];
+        Backend::Ppm.write(&frame, &mut out).unwrap();
+        assert!(out.starts_with(b"P6\n8 8\n255\n"));
+        assert_eq!(out.len(), 11 + 8 * 8 * 3);
+        assert_eq!(&out[out.len() - 3..], &RED);
+        let mut out = vec![];
+        Backend::Png.write(&frame, &mut out).unwrap();
+        assert!(out.starts_with(b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x08\0\0\0\x08"));
+        assert!(out.ends_with(b"IEND\xae\x42\x60\x82"));
+        // 8 scanlines of a filter byte and 8 RGB pixels, stored in a single block
+        let stored = 8 * (1 + 8 * 3);
+        assert_eq!(out.len(), 8 + 25 + (12 + 2 + 5 + stored + 4) + 12);
+    }
+}
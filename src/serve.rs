@@ -0,0 +1,246 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    panic::{self, AssertUnwindSafe},
+};
+
+use crate::{days::DAY_COUNT, try_solve, util::panic_message, Task};
+
+/// Largest accepted request body, real inputs are far smaller.
+const MAX_BODY: usize = 4 << 20;
+/// Largest accepted request line and headers together.
+const MAX_HEADER: usize = 8 << 10;
+
+/// Serve solvers over HTTP until the listener fails. Every connection is handled on the rayon pool.
+///
+/// - `GET /days` lists the available days
+/// - `POST /day/{n}?task=1|2|both` solves the input sent as body
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = stream?;
+        rayon::spawn(move || {
+            if let Err(e) = handle_connection(stream) {
+                eprintln!("error handling request: {e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream) -> io::Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut head = (&mut reader).take(MAX_HEADER as u64);
+    // a line cut off by the limit means that the request line and headers are too large
+    let mut too_large = false;
+    let mut request_line = String::new();
+    head.read_line(&mut request_line)?;
+    too_large |= head.limit() == 0 && !request_line.ends_with('\n');
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default().to_string();
+    // read headers, only the content length is needed
+    let mut content_length = String::from("0");
+    while !too_large {
+        let mut line = String::new();
+        head.read_line(&mut line)?;
+        too_large = head.limit() == 0 && !line.ends_with('\n');
+        if too_large || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().to_string();
+            }
+        }
+    }
+    if too_large {
+        let (status, json) = error(431, &format!("headers larger than {MAX_HEADER} bytes"));
+        return respond(stream, status, &json);
+    }
+    let Ok(content_length) = content_length.parse::<usize>() else {
+        let (status, json) = error(400, &format!("invalid content length '{content_length}'"));
+        return respond(stream, status, &json);
+    };
+    if content_length > MAX_BODY {
+        let (status, json) = error(413, &format!("body larger than {MAX_BODY} bytes"));
+        return respond(stream, status, &json);
+    }
+    let mut body = vec![];
+    reader.take(content_length as u64).read_to_end(&mut body)?;
+    if body.len() < content_length {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    let (status, json) = handle(&method, &target, &body);
+    respond(stream, status, &json)
+}
+
+fn respond(mut stream: TcpStream, status: u16, json: &str) -> io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        _ => "Internal Server Error",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{json}",
+        json.len()
+    )?;
+    stream.flush()
+}
+
+/// Route a request and return the status code and json body.
+fn handle(method: &str, target: &str, body: &[u8]) -> (u16, String) {
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    match (method, segments.as_slice()) {
+        ("GET", ["days"]) => {
+            let days = (1..=DAY_COUNT)
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            (200, format!("{{\"days\":[{days}]}}"))
+        }
+        ("POST", ["day", day]) => {
+            let Some(day) = day.parse().ok().filter(|d| (1..=DAY_COUNT).contains(d)) else {
+                return error(404, &format!("unknown day '{day}'"));
+            };
+            let task = match query_param(query, "task").unwrap_or("both") {
+                "1" => Task::One,
+                "2" => Task::Two,
+                "both" => Task::Both,
+                other => return error(400, &format!("invalid task '{other}'")),
+            };
+            let Ok(input) = std::str::from_utf8(body) else {
+                return error(400, "input is not valid utf-8");
+            };
+            solve_request(day, input, task)
+        }
+        (_, ["days"] | ["day", _]) => error(405, "method not allowed"),
+        _ => error(404, &format!("unknown path '{path}'")),
+    }
+}
+
+fn solve_request(day: usize, input: &str, task: Task) -> (u16, String) {
+    // malformed input is rejected by the parsers, a panic is a bug in the solver
+    match panic::catch_unwind(AssertUnwindSafe(|| try_solve(day, input, task))) {
+        Ok(Ok((res1, res2, timings))) => {
+            (
+                200,
+                format!(
+                    "{{\"day\":{day},\"result1\":{},\"result2\":{},\"timings\":{{\"parsing_us\":{},\"task1_us\":{},\"task2_us\":{}}}}}",
+                    json_string(&res1),
+                    json_string(&res2),
+                    timings.parsing.as_micros(),
+                    timings.task1.as_micros(),
+                    timings.task2.as_micros()
+                ),
+            )
+        }
+        Ok(Err(e)) => error(400, &e.to_string()),
+        Err(e) => error(500, panic_message(&*e)),
+    }
+}
+
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn error(status: u16, msg: &str) -> (u16, String) {
+    (status, format!("{{\"error\":{}}}", json_string(msg)))
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use std::{net::TcpListener, thread};
+
+    use super::*;
+
+    fn request(addr: &str, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn localhost() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        thread::spawn(move || serve(listener));
+
+        let response = request(&addr, "GET /days HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        let days = (1..=DAY_COUNT).map(|d| d.to_string()).collect::<Vec<_>>();
+        assert!(response.ends_with(&format!("{{\"days\":[{}]}}", days.join(","))));
+
+        let input = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+        let response = request(
+            &addr,
+            &format!(
+                "POST /day/1?task=1 HTTP/1.1\r\nContent-Length: {}\r\n\r\n{input}",
+                input.len()
+            ),
+        );
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.contains("{\"day\":1,\"result1\":\"142\",\"result2\":\"\","));
+
+        let response = request(&addr, "POST /day/99 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 404"));
+        let response = request(&addr, "POST /day/1?task=3 HTTP/1.1\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 400"));
+        let response = request(
+            &addr,
+            "POST /day/1 HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n",
+        );
+        assert!(
+            response.starts_with("HTTP/1.1 413 Payload Too Large"),
+            "{response}"
+        );
+        let response = request(&addr, "POST /day/1 HTTP/1.1\r\nContent-Length: 1x\r\n\r\n");
+        assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+        assert!(
+            response.contains("invalid content length '1x'"),
+            "{response}"
+        );
+        // exactly the limit without the end of the headers, so the server reads all of it
+        let start = "GET /days HTTP/1.1\r\nX-Padding: ";
+        let padding = "a".repeat(MAX_HEADER - start.len() - 2);
+        let response = request(&addr, &format!("{start}{padding}\r\n"));
+        assert!(
+            response.starts_with("HTTP/1.1 431 Request Header Fields Too Large"),
+            "{response}"
+        );
+    }
+
+    #[test]
+    fn escape_json() {
+        assert_eq!(json_string("a\"b\\\n"), "\"a\\\"b\\\\\\n\"");
+    }
+}
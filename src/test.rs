@@ -1,8 +1,19 @@
-use std::time::Duration;
+use std::{fs, panic, time::Duration};
 
-use aoc23::{calc_day, Task};
+use aoc23::{
+    cache::{self, AnswerCache, CacheMode},
+    calc_day, calc_day_with, calibration_with, digit_words, explain, explore, ffi, find_input,
+    generate::generate,
+    read_input, solve, try_solve, Area, Bag, ColorStats, Game, RunOptions, Task, Warning,
+    DAY_COUNT,
+};
+use rayon::prelude::*;
 
 fn test_day(day: usize, exp1: &str, exp2: &str) {
+    test_input(day, false, exp1, exp2)
+}
+
+fn test_input(day: usize, test: bool, exp1: &str, exp2: &str) {
     let mut res1 = "".into();
     let mut res2 = "".into();
     let mut duration = Duration::default();
@@ -11,7 +22,7 @@ fn test_day(day: usize, exp1: &str, exp2: &str) {
         &mut res1,
         &mut res2,
         &mut duration,
-        false,
+        test,
         Task::Both,
         false,
     );
@@ -24,11 +35,127 @@ pub fn day1() {
     test_day(1, "54450", "54265")
 }
 
+#[test]
+pub fn day1_examples() {
+    // the example of part 2 has a line without digits for part 1
+    let part1 = "1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n";
+    let part2 = "two1nine\neightwothree\nabcone2threexyz\nxtwone3four\n4nineeightseven2\nzoneight234\n7pqrstsixteen\n";
+    assert_eq!(solve(1, part1, Task::Both), ("142".into(), "142".into()));
+    assert_eq!(solve(1, part2, Task::Both), ("209".into(), "281".into()));
+    let skipped = explain(1, part2).unwrap().unwrap();
+    assert_eq!(skipped.sections[0].rows, [["2", "eightwothree"]]);
+    assert!(skipped.sections[1].rows.is_empty());
+}
+
+#[test]
+pub fn day1_digit_words() {
+    let sum = |input, words| calibration_with(input, words).0;
+    let german = digit_words("german").unwrap();
+    let french = digit_words("french").unwrap();
+    assert_eq!(sum("zweifünf\nxeightwo3\n", german), 25 + 33);
+    assert_eq!(sum("deuxhuit\ncinqunx\n", french), 28 + 51);
+    // the english words are those of part 2
+    let example = read_input(1, true);
+    let english = digit_words("english").unwrap();
+    assert_eq!(
+        sum(&example, english).to_string(),
+        solve(1, &example, Task::Two).1
+    );
+    assert_eq!(sum("xeightwo3\n", &[("two", 2), ("ei", 7)]), 73);
+    assert!(digit_words("klingon").is_none());
+    let (_, warnings) = calibration_with("1a\nabc\n", &[]);
+    assert_eq!(
+        warnings,
+        [Warning {
+            line: 2,
+            text: "abc"
+        }]
+    );
+}
+
+#[test]
+pub fn day1_words() {
+    // overlapping words count from both ends, bytes outside of ASCII are matched too
+    let path = std::env::temp_dir().join(format!("aoc23-words-{}.txt", std::process::id()));
+    fs::write(&path, "zweifünf\nxeightwo3\n").unwrap();
+    let commands = format!(
+        "load {}\nwords english\nwords german\nwords fünf=5 two=2\nwords klingon\nwords x=500000000\nquit\n",
+        path.display()
+    );
+    let mut out = vec![];
+    explore(1, true, &mut commands.as_bytes(), &mut out).unwrap();
+    fs::remove_file(&path).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("> 83\n> 58\n> 78\n"), "{out}");
+    assert!(out.contains("unknown language 'klingon'"), "{out}");
+    assert!(out.contains("> usage: words"), "{out}");
+}
+
 #[test]
 pub fn day2() {
     test_day(2, "2169", "60948")
 }
 
+#[test]
+pub fn day2_queries() {
+    // ids come from the text and colors outside of the bag make a game impossible
+    let input = "Game 7: 1 red, 2 yellow\nGame 9: 3 blue, 1 red; 2 green\n";
+    assert_eq!(solve(2, input, Task::Both), ("9".into(), "6".into()));
+    let commands = "possible 12 red, 13 green, 14 blue\nmin 3\nmin 6\nstats\nquit\n";
+    let mut out = vec![];
+    explore(2, true, &mut commands.as_bytes(), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("3 possible games: 1, 2, 5\n"), "{out}");
+    assert!(out.contains("6 blue, 13 green, 20 red\n"), "{out}");
+    assert!(out.contains("usage: min <game id>"), "{out}");
+    assert!(
+        out.contains("red: 11 draws, 61 cubes in total, at most 20"),
+        "{out}"
+    );
+}
+
+#[test]
+pub fn day2_game_model() {
+    let input = read_input(2, true);
+    let games = Game::parse_all(&input).unwrap();
+    let bag = "12 red, 13 green, 14 blue".parse::<Bag>().unwrap();
+    let possible = games.iter().filter(|game| game.possible_with(&bag));
+    assert_eq!(possible.map(Game::id).collect::<Vec<_>>(), [1, 2, 5]);
+    assert_eq!(games[2].min_bag().to_string(), "6 blue, 13 green, 20 red");
+    assert_eq!(games[2].min_bag().count("yellow"), 0);
+    let stats = Game::color_stats(&games);
+    let red = ColorStats {
+        draws: 11,
+        total: 61,
+        max: 20,
+    };
+    assert_eq!(stats["red"], red);
+    // counts of the same color in a draw add up beyond a u32
+    let large = Game::parse_all("Game 1: 4294967295 red, 1 red\n").unwrap();
+    assert_eq!(large[0].min_bag().count("red"), 1 << 32);
+    assert_eq!(Game::color_stats(&large)["red"].total, 1 << 32);
+    let huge = "Game 1: 4294967295 red, 4294967295 green, 4294967295 blue\n";
+    assert_eq!(solve(2, huge, Task::Two).1, "overflow");
+}
+
+#[test]
+pub fn day2_custom_bag() {
+    let solve_with = |bag: &str| {
+        let bag = bag.parse::<Bag>().unwrap();
+        let (mut res1, mut res2, mut time) = (String::new(), String::new(), Duration::default());
+        let options = RunOptions {
+            bag: Some(&bag),
+            ..RunOptions::new(true, Task::One)
+        };
+        calc_day_with(2, &mut res1, &mut res2, &mut time, &options);
+        res1
+    };
+    assert_eq!(solve_with("12 red, 13 green, 14 blue"), "8");
+    assert_eq!(solve_with("20 red, 20 green, 20 blue"), "15");
+    assert_eq!(solve_with("14 red, 3 green, 15 blue"), "12");
+    assert!("12 red, green".parse::<Bag>().is_err());
+}
+
 #[test]
 pub fn day3() {
     test_day(3, "544664", "84495585")
@@ -108,3 +235,426 @@ pub fn day17() {
 pub fn day18() {
     test_day(18, "72821", "127844509405501")
 }
+
+#[test]
+pub fn day19() {
+    test_day(19, "376008", "124078207789312")
+}
+
+#[test]
+pub fn day19_example() {
+    test_input(19, true, "19114", "167409079868000");
+    // the ratings of a part can add up to more than a u32
+    let large = "in{A}\n\n{x=4294967295,m=1,a=0,s=0}\n";
+    assert_eq!(solve(19, large, Task::One).0, "4294967296");
+}
+
+#[test]
+pub fn day19_long_chain() {
+    // every workflow sends the part on to the next one, deeper than the stack allows recursion
+    let chain = (0..100_000).map(|i| format!("w{i}{{x>4000:R,w{}}}\n", i + 1));
+    let input = format!(
+        "in{{w0}}\n{}w100000{{A}}\n\n{{x=1,m=2,a=3,s=4}}\n",
+        chain.collect::<String>()
+    );
+    let (res1, res2, _) = try_solve(19, &input, Task::Both).unwrap();
+    assert_eq!(res1, "10");
+    assert_eq!(res2, 4000u64.pow(4).to_string());
+}
+
+// There are no real inputs for days 20 to 25 in the repository, so they are tested with the
+// examples of the puzzles. Values the puzzles don't give are regression snapshots of what
+// these solvers answer, not verified answers.
+
+#[test]
+pub fn day20_examples() {
+    // the example has no rx, so part 2 can't be reached
+    test_input(20, true, "11687500", "unreachable");
+    let input = "broadcaster -> a, b, c\n%a -> b\n%b -> c\n%c -> inv\n&inv -> a\n";
+    assert_eq!(solve(20, input, Task::One).0, "32000000");
+    // a conjunction feeding itself pulses forever
+    let looping = "broadcaster -> a\n&a -> a, rx\n";
+    assert_eq!(
+        solve(20, looping, Task::Both),
+        ("unbounded".into(), "unbounded".into())
+    );
+    let mut dot = vec![];
+    explore(20, true, &mut "dot\nquit\n".as_bytes(), &mut dot).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(dot.contains("digraph modules {"), "{dot}");
+    assert!(dot.contains("\"inv\" [shape=diamond];\n"), "{dot}");
+    assert!(dot.contains("\"con\" -> \"output\";\n"), "{dot}");
+}
+
+#[test]
+pub fn day21_example() {
+    // the puzzle gives the example counts for 6 to 5000 steps, the step counts of the parts
+    // only apply to the real input, and the extrapolation has to agree with walking the
+    // infinite map where that is still fast
+    let published = [
+        (6, 16),
+        (10, 50),
+        (50, 1594),
+        (100, 6536),
+        (500, 167004),
+        (1000, 668697),
+        (5000, 16733044),
+    ];
+    let mut commands = String::new();
+    for (steps, _) in published {
+        commands += &format!("reach {steps}\n");
+        if steps <= 500 {
+            commands += &format!("reach {steps} brute\n");
+        }
+    }
+    let mut out = vec![];
+    explore(21, true, &mut (commands + "quit\n").as_bytes(), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    for (steps, plots) in published {
+        let line = format!("{plots} plots reachable in exactly {steps} steps");
+        let count = out.matches(&line).count();
+        assert_eq!(count, if steps <= 500 { 2 } else { 1 }, "{out}");
+    }
+    let ragged = try_solve(21, "#\n#S\n", Task::Both).unwrap_err();
+    assert_eq!(
+        ragged.to_string(),
+        "parse error at line 2, column 1: row has width 2, expected 1"
+    );
+}
+
+#[test]
+pub fn day22_example() {
+    test_input(22, true, "5", "7");
+    let mut out = vec![];
+    explore(22, true, &mut "side\nquit\n".as_bytes(), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(
+        out.contains("FFF    F \nD E   ???\n???   B C\n A    AAA\n---   ---\n"),
+        "{out}"
+    );
+    let huge = try_solve(22, "0,0,1~100000,100000,1\n", Task::Both);
+    assert_eq!(huge.unwrap_err().message, "brick too large");
+}
+
+#[test]
+pub fn day23_example() {
+    test_input(23, true, "94", "154");
+    let (res1, res2, _) = try_solve(23, "#.#\n", Task::Both).unwrap();
+    assert_eq!((res1.as_str(), res2.as_str()), ("0", "0"));
+}
+
+#[test]
+pub fn day24_example() {
+    // the example counts crossings in a smaller area than the real input
+    let area = Some("7,27".parse::<Area>().unwrap());
+    let (mut res1, mut res2, mut time) = (String::new(), String::new(), Duration::default());
+    let options = RunOptions {
+        area,
+        ..RunOptions::new(true, Task::Both)
+    };
+    calc_day_with(24, &mut res1, &mut res2, &mut time, &options);
+    assert_eq!((res1.as_str(), res2.as_str()), ("2", "47"));
+    // without it the answer doesn't depend on whether debug output is on
+    let example = read_input(24, true);
+    assert_eq!(solve(24, &example, Task::One).0, "0");
+    assert!("27,7".parse::<Area>().is_err());
+}
+
+#[test]
+pub fn day25_example() {
+    test_input(25, true, "54", "Merry Christmas");
+}
+
+#[test]
+pub fn input_normalization() {
+    (1..=DAY_COUNT).into_par_iter().for_each(|day| {
+        // days without a real input are checked with the example
+        let input = find_input(day, false).unwrap_or_else(|| read_input(day, true));
+        let expected = solve(day, &input, Task::Both);
+        let trimmed = input.trim_end();
+        let variants = [
+            trimmed.to_string(),
+            format!("{trimmed}\n\n"),
+            format!("{trimmed}\n").replace('\n', "\r\n"),
+            format!("\u{feff}{trimmed}\n"),
+        ];
+        variants.par_iter().for_each(|variant| {
+            assert_eq!(
+                expected,
+                solve(day, variant, Task::Both),
+                "day {day} gave different results for {:?}",
+                &variant[variant.len().saturating_sub(8)..]
+            );
+        });
+    });
+}
+
+#[test]
+pub fn explore_commands() {
+    let commands = "1\npart {x=787,m=2655,a=1222,s=2876}\ndebug\ntimes\nfoo\nquit\n";
+    let mut out = vec![];
+    explore(19, true, &mut commands.as_bytes(), &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("part 1: 19114"), "{out}");
+    assert!(out.contains("in -> qqz -> qs -> lnx -> A"), "{out}");
+    assert!(out.contains("debug output off"), "{out}");
+    assert!(out.contains("task 2: not run"), "{out}");
+    assert!(out.contains("unknown command 'foo'"), "{out}");
+}
+
+#[test]
+pub fn fuzz_regressions() {
+    // inputs that crashed or hung a fuzz target with the answers or the parse error they give now
+    // the two answers or the parse error
+    type Outcome = Result<(&'static str, &'static str), &'static str>;
+    let expected: [(&str, Outcome); 20] = [
+        ("day02/count-overflow", Ok(("0", "0"))),
+        (
+            "day02/crash-b17b3ee2dd3d092fe154756d418607cfb085d8ba",
+            Err("parse error at line 1, column 13: unexpected input ''"),
+        ),
+        (
+            "day05/crash-fe0e927fde303d5d64e48634005c4c557694ece1",
+            Ok(("82", "no solution")),
+        ),
+        (
+            "day05/timeout-eb3e03253f4ae7a93955d0116b2fe5e7fa18ab69",
+            Ok(("88000000000456", "88000000000549")),
+        ),
+        (
+            "day06/crash-0021ba820a0c89a9f027e995b22279c2544e5844",
+            Ok(("0", "7483")),
+        ),
+        ("day06/overflow", Ok(("overflow", "overflow"))),
+        (
+            "day07/unknown-card",
+            Err("parse error at line 2, column 10: unexpected input ''"),
+        ),
+        (
+            "day08/crash-0afb4af2270745210314b3478f0e8d113212cf24",
+            Ok(("unreachable", "6")),
+        ),
+        (
+            "day08/crash-dbaa842d812ccd19f6a9900dcff74957a6e4637c",
+            Err("parse error at line 7, column 1: duplicate node '11B'"),
+        ),
+        ("day09/overflow", Ok(("overflow", "overflow"))),
+        (
+            "day10/unknown-pipe",
+            Err("parse error at line 4, column 5: unexpected character '*'"),
+        ),
+        (
+            "day12/crash-acf967073e631a7c2dd9c3c78dd7b6a7cbe1ee58",
+            Err("parse error at line 1, column 28: unexpected input ',0000000000000000000000018446744073709551615'"),
+        ),
+        ("day13/no-reflection", Ok(("5", "1"))),
+        (
+            "day15/crash-31291a136b9d47684fd6f4e862aed5345c559d16",
+            Err("parse error at line 1, column 28: unexpected input 't=9,ab=5,pc-,pc=6,ot=7'"),
+        ),
+        (
+            "day17/crash-e54b5656b017b8d2e2c3ad329cb42c483e0e5ad1",
+            Ok(("unreachable", "15")),
+        ),
+        (
+            "day18/crash-25af3ddd9d7314724b1876953beead0f303dd895",
+            Err("parse error at line 3, column 14: unexpected input ''"),
+        ),
+        (
+            "day19/crash-fd337f7f8ddea5558a895accfd45f9941c2ba7e1",
+            Ok(("19114", "167409079868000")),
+        ),
+        (
+            "day19/rating-overflow",
+            Ok(("4294967296", "256000000000000")),
+        ),
+        ("day20/pulse-loop", Ok(("unbounded", "unreachable"))),
+        (
+            "day22/huge-brick",
+            Err("parse error at line 1, column 1: brick too large"),
+        ),
+    ];
+    let mut files = vec![];
+    for day in 1..=DAY_COUNT {
+        let Ok(entries) = fs::read_dir(format!("fuzz/regressions/day{day:02}")) else {
+            continue;
+        };
+        for entry in entries {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            files.push(format!("day{day:02}/{name}"));
+        }
+    }
+    files.sort();
+    let names = expected.iter().map(|(name, _)| name.to_string());
+    assert_eq!(
+        files,
+        names.collect::<Vec<_>>(),
+        "every file needs an expectation"
+    );
+    for (name, expected) in expected {
+        let day = name[3..5].parse().unwrap();
+        let input = fs::read_to_string(format!("fuzz/regressions/{name}")).unwrap();
+        let result = panic::catch_unwind(|| try_solve(day, &input, Task::Both));
+        let result = result.unwrap_or_else(|_| panic!("{name} panicked"));
+        match (result, expected) {
+            (Ok((res1, res2, _)), Ok(answers)) => {
+                assert_eq!((res1.as_str(), res2.as_str()), answers, "{name}")
+            }
+            (Err(e), Err(message)) => assert_eq!(e.to_string(), message, "{name}"),
+            (result, _) => panic!(
+                "{name}: unexpected {:?}",
+                result.map(|(res1, res2, _)| (res1, res2))
+            ),
+        }
+    }
+}
+
+#[test]
+pub fn arithmetic_overflow() {
+    // well formed input with answers too large for the integers of the solvers
+    let answers = |day, input| {
+        let (res1, res2, _) = try_solve(day, input, Task::Both).unwrap();
+        (res1, res2)
+    };
+    let overflow = || (String::from("overflow"), String::from("overflow"));
+    assert_eq!(
+        answers(6, "Time: 99999999999 99999999999\nDistance: 1 1\n"),
+        overflow()
+    );
+    let long_race = answers(6, "Time: 99999999999\nDistance: 1\n");
+    assert_eq!(long_race.0, "99999999998");
+    assert_eq!(answers(9, "2147483647 -2147483648\n"), overflow());
+}
+
+#[cfg(feature = "reference")]
+#[test]
+pub fn cross_check() {
+    use aoc23::reference::{self, Check};
+    for day in reference::DAYS {
+        for check in reference::cross_check(day, &read_input(day, true)).unwrap() {
+            assert!(
+                !matches!(check, Check::Disagree { .. }),
+                "day {day}: {check:?}"
+            );
+        }
+    }
+}
+
+#[test]
+pub fn generated_inputs() {
+    for day in 1..=DAY_COUNT {
+        for seed in 0..3 {
+            let input = generate(day, 30, seed);
+            assert_eq!(
+                input,
+                generate(day, 30, seed),
+                "day {day} is not reproducible"
+            );
+            if let Err(e) = try_solve(day, &input, Task::Both) {
+                panic!("day {day} seed {seed}: {e}\n{input}");
+            }
+        }
+    }
+}
+
+#[test]
+pub fn explanations() {
+    let cards = explain(4, &read_input(4, true)).unwrap().unwrap();
+    let copies = cards.sections[0]
+        .rows
+        .iter()
+        .map(|row| row[3].parse::<u32>().unwrap());
+    assert_eq!(copies.sum::<u32>(), 30);
+    let hands = explain(7, &read_input(7, true)).unwrap().unwrap();
+    assert_eq!(hands.sections[1].rows[4][1..3], ["KTJJT", "Four"]);
+    let accepted = explain(19, &read_input(19, true)).unwrap().unwrap();
+    assert_eq!(accepted.sections[0].rows.len(), 3);
+    assert!(explain(6, &read_input(6, true)).unwrap().is_none());
+}
+
+#[test]
+pub fn answer_cache() {
+    let path = std::env::temp_dir().join(format!("aoc23-cache-{}.tsv", std::process::id()));
+    let cache = AnswerCache::load(&path, CacheMode::Use).unwrap();
+    let (mut res1, mut res2, mut time) = (String::new(), String::new(), Duration::default());
+    let options = RunOptions {
+        cache: Some(&cache),
+        ..RunOptions::new(false, Task::Both)
+    };
+    calc_day_with(4, &mut res1, &mut res2, &mut time, &options);
+    cache.save().unwrap();
+
+    let cache = AnswerCache::load(&path, CacheMode::Use).unwrap();
+    let entries = cache.entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!((&entries[0].result1, &entries[0].result2), (&res1, &res2));
+    assert_eq!(
+        entries[0].fingerprint,
+        cache::fingerprint(4, &read_input(4, false), &options)
+    );
+    // a hit returns the stored answers and solve time without solving again
+    let contents = fs::read_to_string(&path).unwrap();
+    let stored = format!("{}\t{}\t{}\n", res1, res2, entries[0].time.as_micros());
+    fs::write(
+        &path,
+        contents.replace(&stored, "stored 1\tstored 2\t1234\n"),
+    )
+    .unwrap();
+    let cache = AnswerCache::load(&path, CacheMode::Use).unwrap();
+    let options = RunOptions {
+        cache: Some(&cache),
+        ..options
+    };
+    calc_day_with(4, &mut res1, &mut res2, &mut time, &options);
+    assert_eq!((res1.as_str(), res2.as_str()), ("stored 1", "stored 2"));
+    assert_eq!(time, Duration::from_micros(1234));
+    // entries of inputs or solvers that changed are stale
+    let fingerprint = format!("{:016x}", entries[0].fingerprint);
+    let contents = fs::read_to_string(&path).unwrap();
+    fs::write(&path, contents.replace(&fingerprint, "0123456789abcdef")).unwrap();
+    let cache = AnswerCache::load(&path, CacheMode::Use).unwrap();
+    assert_eq!(cache.prune(), 1);
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+pub fn c_header() {
+    let header = fs::read_to_string("ffi/aoc23.h").unwrap();
+    assert!(
+        header == ffi::header(),
+        "ffi/aoc23.h is outdated, regenerate it with aoc23::ffi::header"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+pub fn c_smoke_test() {
+    use std::{env, process::Command};
+    // the cdylib is built next to the test executable
+    let exe = env::current_exe().unwrap();
+    let lib_dir = exe.parent().unwrap();
+    let smoke = env::temp_dir().join(format!("aoc23-smoke-{}", std::process::id()));
+    let compiler = cc::Build::new()
+        .target(env!("AOC23_TARGET"))
+        .host(env!("AOC23_HOST"))
+        .opt_level(0)
+        .cargo_metadata(false)
+        .get_compiler();
+    let status = compiler
+        .to_command()
+        .args(["-I", "ffi", "ffi/smoke.c", "-o"])
+        .arg(&smoke)
+        .arg("-L")
+        .arg(lib_dir)
+        .arg("-laoc23")
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .status()
+        .unwrap();
+    assert!(status.success(), "compiling ffi/smoke.c failed");
+    let output = Command::new(&smoke).output().unwrap();
+    fs::remove_file(smoke).unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stdout}{stderr}");
+    assert_eq!(stdout.trim(), "ok");
+}
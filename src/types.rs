@@ -5,10 +5,25 @@ pub enum Task {
     Both,
 }
 
+/// Time spent in each phase of solving a day.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Timings {
+    pub parsing: Duration,
+    pub task1: Duration,
+    pub task2: Duration,
+}
+
 pub trait DaySolver<'a> {
     type Input;
 
-    fn parse_input(input: &'a str, test: bool) -> Self::Input;
+    /// how the raw input is normalized before it is passed to `parse_input`
+    const NORMALIZE: Normalize = Normalize::DEFAULT;
+
+    /// help lines for the commands handled by `explore`
+    const EXPLORE_HELP: &'static str = "";
+
+    /// Parse the normalized input, malformed input is reported instead of panicking.
+    fn parse_input(input: &'a str, test: bool) -> Result<Self::Input, ParseError>;
 
     fn solve(
         &self,
@@ -17,11 +32,11 @@ pub trait DaySolver<'a> {
         test: bool,
         task: Task,
         print_times: bool,
-    ) -> (String, String) {
+    ) -> Result<(String, String, Timings), ParseError> {
         let mut res1 = "".into();
         let mut res2 = "".into();
         let start = Instant::now();
-        let input = Self::parse_input(input, test);
+        let input = Self::parse_input(input, test)?;
         let parsing = start.elapsed();
         let start = Instant::now();
         if !matches!(task, Task::Two) {
@@ -38,11 +53,26 @@ pub trait DaySolver<'a> {
                 "day: {day:>2} parsing: {parsing:>8.2?}, task 1: {t1:>8.2?}, task 2: {t2:>8.2?}"
             );
         }
-        (res1, res2)
+        let timings = Timings {
+            parsing,
+            task1: t1,
+            task2: t2,
+        };
+        Ok((res1, res2, timings))
     }
 
     fn solve1(&self, input: &Self::Input, test: bool) -> String;
     fn solve2(&self, input: &Self::Input, test: bool) -> String;
+
+    /// Day specific commands for the explore mode, returns None for unknown commands.
+    fn explore(&self, _input: &Self::Input, _command: &str, _args: &[&str]) -> Option<String> {
+        None
+    }
+
+    /// Trace of how the answers are reached for `--explain`, None if the day has none.
+    fn explain(&self, _input: &Self::Input) -> Option<Explanation> {
+        None
+    }
 }
 
 macro_rules! test_print {
@@ -52,84 +82,180 @@ macro_rules! test_print {
         }
     };
 }
-use std::{fmt::Display, time::Instant};
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    time::{Duration, Instant},
+};
+
+use num::{PrimInt, Signed};
 
+use crate::util::Normalize;
+
+pub(crate) use crate::{explain::Explanation, parse::ParseError};
 pub(crate) use test_print;
 
-#[derive(Debug, PartialEq, Copy, Clone)]
-pub struct Direction {
-    pub x: i64,
-    pub y: i64,
+/// Integer types usable as coordinates of positions and directions.
+pub trait Coord: PrimInt + Signed + Hash + Debug + Display {
+    const ZERO: Self;
+    const ONE: Self;
+    const NEG_ONE: Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty),*) => {
+        $(
+            impl Coord for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+                const NEG_ONE: Self = -1;
+            }
+        )*
+    };
+}
+
+impl_coord!(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct Direction<T = i64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Direction {
-    pub fn new(x: i64, y: i64) -> Self {
+impl<T: Coord> Direction<T> {
+    pub const UP: Self = Self::new(T::ZERO, T::NEG_ONE);
+    pub const DOWN: Self = Self::new(T::ZERO, T::ONE);
+    pub const LEFT: Self = Self::new(T::NEG_ONE, T::ZERO);
+    pub const RIGHT: Self = Self::new(T::ONE, T::ZERO);
+    /// clockwise, starting with up
+    pub const ALL: [Self; 4] = [Self::UP, Self::RIGHT, Self::DOWN, Self::LEFT];
+    /// clockwise, starting with up
+    pub const ALL_DIAGONAL: [Self; 8] = [
+        Self::UP,
+        Self::new(T::ONE, T::NEG_ONE),
+        Self::RIGHT,
+        Self::new(T::ONE, T::ONE),
+        Self::DOWN,
+        Self::new(T::NEG_ONE, T::ONE),
+        Self::LEFT,
+        Self::new(T::NEG_ONE, T::NEG_ONE),
+    ];
+
+    pub const fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
+
+    /// Rotate by 90 degrees counterclockwise on screen (y pointing down).
+    pub fn turn_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /// Rotate by 90 degrees clockwise on screen (y pointing down).
+    pub fn turn_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    pub fn reverse(self) -> Self {
+        -self
+    }
 }
 
-impl PartialEq<(i64, i64)> for Direction {
-    fn eq(&self, other: &(i64, i64)) -> bool {
+impl<T: Coord> PartialEq<(T, T)> for Direction<T> {
+    fn eq(&self, other: &(T, T)) -> bool {
         self.x == other.0 && self.y == other.1
     }
 }
 
-impl<T: Into<i64> + Copy> std::ops::Mul<T> for Direction {
+impl<T: Coord, U: Into<T>> Mul<U> for Direction<T> {
     type Output = Self;
 
-    fn mul(self, rhs: T) -> Self::Output {
+    fn mul(self, rhs: U) -> Self::Output {
+        let rhs = rhs.into();
         Self {
-            x: self.x * rhs.into(),
-            y: self.y * rhs.into(),
+            x: self.x * rhs,
+            y: self.y * rhs,
         }
     }
 }
 
-impl Display for Direction {
+impl<T: Coord> Neg for Direction<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Coord> Add for Direction<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> Display for Direction<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let c = match (self.x, self.y) {
-            (0, -1) => '^',
-            (-1, 0) => '<',
-            (1, 0) => '>',
-            (0, 1) => 'v',
-            _ => '?',
+        let c = match *self {
+            dir if dir == Self::UP => '^',
+            dir if dir == Self::LEFT => '<',
+            dir if dir == Self::RIGHT => '>',
+            dir if dir == Self::DOWN => 'v',
+            dir => return write!(f, "({}, {})", dir.x, dir.y),
         };
         write!(f, "{}", c)
     }
 }
 
 #[derive(Debug, PartialEq, Copy, Clone, Eq, Hash)]
-pub struct Position {
-    pub x: i64,
-    pub y: i64,
+pub struct Position<T = i64> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Position {
-    pub fn new<T: Into<i64>>(x: T, y: T) -> Self {
-        Self {
-            x: x.into(),
-            y: y.into(),
-        }
+impl<T: Coord> Position<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// Create a position from grid indices, returns None if they don't fit into `T`.
+    pub fn from_index(x: usize, y: usize) -> Option<Self> {
+        Some(Self::new(T::from(x)?, T::from(y)?))
+    }
+
+    /// Convert to grid indices, returns None for negative coordinates.
+    pub fn to_index(&self) -> Option<(usize, usize)> {
+        Some((self.x.to_usize()?, self.y.to_usize()?))
+    }
+
+    /// Look up the position in a grid of rows, returns None if it is outside.
+    pub fn get<'g, V>(&self, grid: &'g [Vec<V>]) -> Option<&'g V> {
+        let (x, y) = self.to_index()?;
+        grid.get(y)?.get(x)
     }
 
     pub fn distance(&self, other: &Self) -> usize {
-        (self.x - other.x).unsigned_abs() as usize + (self.y - other.y).unsigned_abs() as usize
+        ((self.x - other.x).abs() + (self.y - other.y).abs())
+            .to_usize()
+            .unwrap()
+    }
+
+    /// The four orthogonal neighbors, clockwise starting with up.
+    pub fn neighbors(&self) -> [Self; 4] {
+        Direction::ALL.map(|dir| *self + dir)
     }
 
-    pub(crate) fn neighbors(&self) -> [Position; 4] {
-        [
-            Position::new(self.x, self.y - 1),
-            Position::new(self.x + 1, self.y),
-            Position::new(self.x, self.y + 1),
-            Position::new(self.x - 1, self.y),
-        ]
+    /// All eight neighbors including diagonals, clockwise starting with up.
+    pub fn neighbors8(&self) -> [Self; 8] {
+        Direction::ALL_DIAGONAL.map(|dir| *self + dir)
     }
 }
 
-impl std::ops::Add<Direction> for Position {
+impl<T: Coord> Add<Direction<T>> for Position<T> {
     type Output = Self;
 
-    fn add(self, rhs: Direction) -> Self::Output {
+    fn add(self, rhs: Direction<T>) -> Self::Output {
         Self {
             x: self.x + rhs.x,
             y: self.y + rhs.y,
@@ -137,14 +263,195 @@ impl std::ops::Add<Direction> for Position {
     }
 }
 
-impl std::ops::AddAssign<Direction> for Position {
-    fn add_assign(&mut self, rhs: Direction) {
+impl<T: Coord> AddAssign<Direction<T>> for Position<T> {
+    fn add_assign(&mut self, rhs: Direction<T>) {
         *self = *self + rhs;
     }
 }
 
-impl Display for Position {
+impl<T: Coord> Sub<Direction<T>> for Position<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Direction<T>) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T: Coord> SubAssign<Direction<T>> for Position<T> {
+    fn sub_assign(&mut self, rhs: Direction<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Sub for Position<T> {
+    type Output = Direction<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Direction::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord> Display for Position<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[{}, {}]", self.x, self.y)
     }
 }
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct Direction3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Direction3<T> {
+    pub const UP: Self = Self::new(T::ZERO, T::ZERO, T::ONE);
+    pub const DOWN: Self = Self::new(T::ZERO, T::ZERO, T::NEG_ONE);
+    pub const ALL: [Self; 6] = [
+        Self::new(T::ONE, T::ZERO, T::ZERO),
+        Self::new(T::NEG_ONE, T::ZERO, T::ZERO),
+        Self::new(T::ZERO, T::ONE, T::ZERO),
+        Self::new(T::ZERO, T::NEG_ONE, T::ZERO),
+        Self::UP,
+        Self::DOWN,
+    ];
+
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Coord, U: Into<T>> Mul<U> for Direction3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: U) -> Self::Output {
+        let rhs = rhs.into();
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Coord> Neg for Direction3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T: Coord> Display for Direction3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct Position3<T = i64> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Position3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn distance(&self, other: &Self) -> usize {
+        ((self.x - other.x).abs() + (self.y - other.y).abs() + (self.z - other.z).abs())
+            .to_usize()
+            .unwrap()
+    }
+
+    /// The six orthogonal neighbors.
+    pub fn neighbors(&self) -> [Self; 6] {
+        Direction3::ALL.map(|dir| *self + dir)
+    }
+}
+
+impl<T: Coord> Add<Direction3<T>> for Position3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Direction3<T>) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Coord> AddAssign<Direction3<T>> for Position3<T> {
+    fn add_assign(&mut self, rhs: Direction3<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord> Sub<Direction3<T>> for Position3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Direction3<T>) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T: Coord> SubAssign<Direction3<T>> for Position3<T> {
+    fn sub_assign(&mut self, rhs: Direction3<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Sub for Position3<T> {
+    type Output = Direction3<T>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Direction3::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Coord> Display for Position3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}, {}]", self.x, self.y, self.z)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn direction_rotation() {
+        let dir: Direction<i32> = Direction::UP;
+        assert_eq!(dir.turn_right(), Direction::RIGHT);
+        assert_eq!(dir.turn_left(), Direction::LEFT);
+        assert_eq!(dir.reverse(), Direction::DOWN);
+        assert_eq!(dir.turn_right().turn_right(), -dir);
+        assert_eq!(
+            Direction::<i8>::ALL.map(|d| d.to_string()),
+            ["^", ">", "v", "<"]
+        );
+        assert_eq!(Direction::new(2, -3).to_string(), "(2, -3)");
+    }
+
+    #[test]
+    fn grid_index() {
+        let grid = vec![vec![1, 2], vec![3, 4]];
+        let pos = Position::<i16>::from_index(1, 0).unwrap();
+        assert_eq!(pos.get(&grid), Some(&2));
+        assert_eq!((pos + Direction::DOWN).to_index(), Some((1, 1)));
+        assert_eq!((pos + Direction::UP).get(&grid), None);
+        assert_eq!((pos + Direction::RIGHT).get(&grid), None);
+        assert_eq!(Position::<i8>::from_index(200, 0), None);
+        assert_eq!(
+            pos.neighbors8()
+                .iter()
+                .filter(|n| n.get(&grid).is_some())
+                .count(),
+            3
+        );
+        assert_eq!(Position::new(4, 1) - pos, Direction::new(3, 1));
+    }
+
+    #[test]
+    fn positions_3d() {
+        let pos = Position3::new(1, 2, 3);
+        assert_eq!(pos + Direction3::DOWN * 3, Position3::new(1, 2, 0));
+        assert_eq!(pos.neighbors().len(), 6);
+        assert_eq!(pos.distance(&Position3::new(0, 0, 0)), 6);
+    }
+}
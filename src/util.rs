@@ -1,17 +1,208 @@
-use std::{fs::File, io::Read};
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::{hash_map::Entry, HashMap},
+    fs::File,
+    hash::Hash,
+    io::Read,
+};
 
+#[cfg(feature = "embed-inputs")]
+include!(concat!(env!("OUT_DIR"), "/embedded_inputs.rs"));
+
+/// Read the input from `./inputs`, falling back to the embedded inputs if the file is missing.
 pub fn read_input(day: usize, test: bool) -> String {
-    let path = format!(
-        "./inputs/day{day:0>2}{}.txt",
-        if test { "_test" } else { "" }
-    );
-    let mut file = File::open(&path).unwrap_or_else(|_| panic!("File '{path}' not found"));
+    find_input(day, test).unwrap_or_else(|| panic!("File '{}' not found", input_path(day, test)))
+}
+
+/// Like [`read_input`], but returns None if there is no input.
+pub fn find_input(day: usize, test: bool) -> Option<String> {
+    let mut file = match File::open(input_path(day, test)) {
+        Ok(file) => file,
+        Err(_) => return embedded_input(day, test).map(String::from),
+    };
     let mut contents = String::new();
     file.read_to_string(&mut contents)
         .expect("Something went wrong reading the file");
-    #[cfg(target_os = "windows")]
+    Some(contents)
+}
+
+fn input_path(day: usize, test: bool) -> String {
+    format!(
+        "./inputs/day{day:0>2}{}.txt",
+        if test { "_test" } else { "" }
+    )
+}
+
+/// Input embedded at compile time, only available with the `embed-inputs` feature.
+pub fn embedded_input(day: usize, test: bool) -> Option<&'static str> {
+    #[cfg(feature = "embed-inputs")]
     {
-        contents = contents.replace("\r\n", "\n");
+        EMBEDDED_INPUTS
+            .iter()
+            .find(|(d, t, _)| *d == day && *t == test)
+            .map(|(_, _, input)| *input)
+    }
+    #[cfg(not(feature = "embed-inputs"))]
+    {
+        let _ = (day, test);
+        None
+    }
+}
+
+/// Least common multiple of all values, zero if one of them is zero and None if it overflows.
+pub(crate) fn lcm(values: impl IntoIterator<Item = usize>) -> Option<usize> {
+    values.into_iter().try_fold(1, |acc: usize, value| {
+        if acc == 0 || value == 0 {
+            return Some(0);
+        }
+        (acc / num::integer::gcd(acc, value)).checked_mul(value)
+    })
+}
+
+/// The state of step `start` comes back every `len` steps.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) struct Cycle {
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Cycle {
+    /// Steps still needed after `step` of the cycle to be in the state of step `target`.
+    pub(crate) fn remaining(&self, step: usize, target: usize) -> usize {
+        (target - step) % self.len
+    }
+}
+
+/// Finds the first state that repeats by remembering at which step each state was seen.
+pub(crate) struct CycleFinder<S> {
+    seen: HashMap<S, usize>,
+}
+
+impl<S: Hash + Eq> CycleFinder<S> {
+    pub(crate) fn new() -> Self {
+        Self {
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Records the state of `step`, returns the cycle if the state was seen before.
+    pub(crate) fn visit(&mut self, state: S, step: usize) -> Option<Cycle> {
+        match self.seen.entry(state) {
+            Entry::Occupied(seen) => Some(Cycle {
+                start: *seen.get(),
+                len: step - seen.get(),
+            }),
+            Entry::Vacant(seen) => {
+                seen.insert(step);
+                None
+            }
+        }
+    }
+}
+
+/// Message of a caught panic, panics without a string payload get a generic one.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| payload.downcast_ref::<&str>().copied())
+        .unwrap_or("solver panicked")
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum TrailingNewline {
+    /// leave the end of the input as it is
+    Keep,
+    /// end the input with exactly one newline
+    Ensure,
+    /// remove all trailing newlines
+    Strip,
+}
+
+/// Describes how a day expects its input before it is parsed.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Normalize {
+    pub strip_bom: bool,
+    pub strip_crlf: bool,
+    pub trailing_newline: TrailingNewline,
+}
+
+impl Normalize {
+    pub const DEFAULT: Self = Self {
+        strip_bom: true,
+        strip_crlf: true,
+        trailing_newline: TrailingNewline::Ensure,
+    };
+
+    pub fn apply<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let mut input = Cow::Borrowed(input);
+        if self.strip_bom {
+            if let Some(stripped) = input.strip_prefix('\u{feff}') {
+                input = Cow::Owned(stripped.to_string());
+            }
+        }
+        if self.strip_crlf && input.contains('\r') {
+            input = Cow::Owned(input.replace("\r\n", "\n"));
+        }
+        match self.trailing_newline {
+            TrailingNewline::Keep => input,
+            TrailingNewline::Ensure if input.ends_with('\n') && !input.ends_with("\n\n") => input,
+            TrailingNewline::Ensure => {
+                let mut trimmed = input.trim_end_matches('\n').to_string();
+                trimmed.push('\n');
+                Cow::Owned(trimmed)
+            }
+            TrailingNewline::Strip => match input {
+                Cow::Borrowed(s) => Cow::Borrowed(s.trim_end_matches('\n')),
+                Cow::Owned(s) => Cow::Owned(s.trim_end_matches('\n').to_string()),
+            },
+        }
+    }
+}
+
+impl Default for Normalize {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalize_input() {
+        let normalize = Normalize::DEFAULT;
+        assert_eq!(normalize.apply("\u{feff}a\r\nb"), "a\nb\n");
+        assert_eq!(normalize.apply("a\nb\n\n\n"), "a\nb\n");
+        assert!(matches!(normalize.apply("a\nb\n"), Cow::Borrowed(_)));
+        let strip = Normalize {
+            trailing_newline: TrailingNewline::Strip,
+            ..Normalize::DEFAULT
+        };
+        assert_eq!(strip.apply("a,b\r\n"), "a,b");
+        let keep = Normalize {
+            strip_bom: false,
+            strip_crlf: false,
+            trailing_newline: TrailingNewline::Keep,
+        };
+        assert_eq!(keep.apply("\u{feff}a\r\n"), "\u{feff}a\r\n");
+    }
+
+    #[test]
+    fn lcm_and_cycles() {
+        assert_eq!(lcm([4, 6, 10]), Some(60));
+        assert_eq!(lcm([0, 0]), Some(0));
+        assert_eq!(lcm([3, 0, 5]), Some(0));
+        assert_eq!(lcm([usize::MAX, 2]), None);
+        let mut finder = CycleFinder::new();
+        let states = [1, 2, 3, 4, 2];
+        let cycle = (0..)
+            .zip(states)
+            .find_map(|(step, state)| finder.visit(state, step));
+        assert_eq!(cycle, Some(Cycle { start: 1, len: 3 }));
+        assert_eq!(cycle.unwrap().remaining(4, 10), 0);
+        assert_eq!(cycle.unwrap().remaining(4, 11), 1);
     }
-    contents
 }